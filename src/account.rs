@@ -1,5 +1,8 @@
 use crate::Hateoas;
-use ipnet::Ipv4Net;
+use crate::links::LinkRel;
+use crate::secret::Secret;
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+use rust_decimal::Decimal;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde")]
@@ -14,6 +17,10 @@ use super::utils;
 /// Account CIDR
 ///
 /// An IP range indicating the whitelisted IP address for connecting to the ATLAS FPS
+///
+/// The range may be either IPv4 or IPv6. Code which previously relied on this dereferencing to an
+/// [`Ipv4Net`] can use [`AccountCidr::as_ipv4`], or match on the [`IpNet`] it now dereferences
+/// to.
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -23,11 +30,94 @@ use super::utils;
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
 pub struct AccountCidr {
     pub name: String,
-    pub cidr: Ipv4Net,
+    pub cidr: IpNet,
+}
+
+impl AccountCidr {
+    /// Whether the address falls within the range
+    ///
+    /// Accepts [`std::net::IpAddr`], [`std::net::Ipv4Addr`] or [`std::net::Ipv6Addr`]. An address
+    /// of a different family than the range is never contained.
+    pub fn contains<A>(&self, addr: &A) -> bool
+    where
+        A: Copy + Into<std::net::IpAddr>,
+    {
+        let addr: std::net::IpAddr = (*addr).into();
+        self.cidr.contains(&addr)
+    }
+
+    /// The range, if it is an IPv4 range
+    pub fn as_ipv4(&self) -> Option<&Ipv4Net> {
+        match &self.cidr {
+            IpNet::V4(net) => Some(net),
+            IpNet::V6(_) => None,
+        }
+    }
+
+    /// The range, if it is an IPv6 range
+    pub fn as_ipv6(&self) -> Option<&Ipv6Net> {
+        match &self.cidr {
+            IpNet::V4(_) => None,
+            IpNet::V6(net) => Some(net),
+        }
+    }
+
+    /// The address of the range
+    ///
+    /// Previously an [`Ipv4Addr`](std::net::Ipv4Addr), through the [`Ipv4Net`] this dereferenced
+    /// to.
+    #[deprecated(since = "4.0.0", note = "use `as_ipv4` or the `cidr` field instead")]
+    pub fn addr(&self) -> std::net::IpAddr {
+        self.cidr.addr()
+    }
+
+    /// The network address of the range
+    ///
+    /// Previously an [`Ipv4Addr`](std::net::Ipv4Addr), through the [`Ipv4Net`] this dereferenced
+    /// to.
+    #[deprecated(since = "4.0.0", note = "use `as_ipv4` or the `cidr` field instead")]
+    pub fn network(&self) -> std::net::IpAddr {
+        self.cidr.network()
+    }
+
+    /// The broadcast address of the range
+    ///
+    /// Previously an [`Ipv4Addr`](std::net::Ipv4Addr), through the [`Ipv4Net`] this dereferenced
+    /// to.
+    #[deprecated(since = "4.0.0", note = "use `as_ipv4` or the `cidr` field instead")]
+    pub fn broadcast(&self) -> std::net::IpAddr {
+        self.cidr.broadcast()
+    }
+
+    /// The netmask of the range
+    ///
+    /// Previously an [`Ipv4Addr`](std::net::Ipv4Addr), through the [`Ipv4Net`] this dereferenced
+    /// to.
+    #[deprecated(since = "4.0.0", note = "use `as_ipv4` or the `cidr` field instead")]
+    pub fn netmask(&self) -> std::net::IpAddr {
+        self.cidr.netmask()
+    }
+
+    /// The hostmask of the range
+    ///
+    /// Previously an [`Ipv4Addr`](std::net::Ipv4Addr), through the [`Ipv4Net`] this dereferenced
+    /// to.
+    #[deprecated(since = "4.0.0", note = "use `as_ipv4` or the `cidr` field instead")]
+    pub fn hostmask(&self) -> std::net::IpAddr {
+        self.cidr.hostmask()
+    }
+
+    /// The range with its host bits cleared
+    ///
+    /// Previously an [`Ipv4Net`], through the [`Ipv4Net`] this dereferenced to.
+    #[deprecated(since = "4.0.0", note = "use `as_ipv4` or the `cidr` field instead")]
+    pub fn trunc(&self) -> IpNet {
+        self.cidr.trunc()
+    }
 }
 
 impl std::ops::Deref for AccountCidr {
-    type Target = Ipv4Net;
+    type Target = IpNet;
 
     fn deref(&self) -> &Self::Target {
         &self.cidr
@@ -41,9 +131,16 @@ impl std::ops::Deref for AccountCidr {
 )]
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+/// A pricing tier of an [`Account`]
+///
+/// The API doesn't document the meaning of either field. [`Account::estimate_cost`] treats
+/// [`Tier::tier`] only as an identifier, and assumes [`Tier::price`] is the price of a billable
+/// minute.
 pub struct Tier {
     pub tier: u32,
-    pub price: f32,
+    /// The price, assumed to be per billable minute, serialized as a JSON number
+    #[cfg_attr(feature = "serde", serde(with = "rust_decimal::serde::float"))]
+    pub price: Decimal,
     #[cfg_attr(
         feature = "serde",
         serde(rename = "_links", with = "utils::links::serde", default)
@@ -80,7 +177,7 @@ pub struct Account {
     #[cfg_attr(feature = "serde", serde(default))]
     pub internal_meta_data: Option<HashMap<String, String>>,
     pub name: String,
-    pub storage_key: String,
+    pub storage_key: Secret,
     #[cfg_attr(feature = "serde", serde(default))]
     pub storage_group: Option<String>,
     pub tiers: Vec<Tier>,
@@ -104,6 +201,15 @@ pub struct Account {
     pub links: HashMap<String, Url>,
 }
 
+impl Account {
+    /// Whether the address falls within one of the account's realtime CIDR ranges
+    pub fn realtime_allows(&self, addr: std::net::IpAddr) -> bool {
+        self.access_realtime_cidr
+            .iter()
+            .any(|cidr| cidr.contains(&addr))
+    }
+}
+
 impl Hateoas for Account {
     fn get_links(&self) -> &HashMap<String, url::Url> {
         &self.links
@@ -112,11 +218,15 @@ impl Hateoas for Account {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::net::Ipv4Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
 
     use super::*;
 
@@ -132,7 +242,36 @@ mod tests {
         assert_eq!(cidr.name, "Test Account");
         assert_eq!(
             cidr.cidr,
-            Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 96), 28)
+            IpNet::from(Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 96), 28))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ipv6_cidr_deserialize() {
+        let json = serde_json::json!({
+            "name": "Test Account",
+            "cidr": "2001:db8::/32"
+        });
+
+        let cidr: AccountCidr = serde_json::from_value(json).unwrap();
+        assert!(cidr.as_ipv4().is_none());
+        assert_eq!(cidr.as_ipv6().unwrap().prefix_len(), 32);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tier_price_is_exact() {
+        let json = serde_json::json!({
+            "tier": 1,
+            "price": 0.1,
+        });
+
+        let tier: Tier = serde_json::from_value(json).unwrap();
+        assert_eq!(tier.price, Decimal::new(1, 1));
+        assert_eq!(
+            serde_json::to_value(&tier).unwrap()["price"],
+            serde_json::json!(0.1)
         );
     }
 
@@ -140,7 +279,7 @@ mod tests {
     fn ip_cidr_check_28() {
         let cidr = AccountCidr {
             name: "Test".into(),
-            cidr: Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 96), 28),
+            cidr: Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 96), 28).into(),
         };
 
         assert!(cidr.contains(&Ipv4Addr::new(192, 168, 1, 99)));
@@ -152,7 +291,7 @@ mod tests {
     fn ip_cidr_check_32() {
         let cidr = AccountCidr {
             name: "Test".into(),
-            cidr: Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 96), 32),
+            cidr: Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 96), 32).into(),
         };
 
         assert!(cidr.contains(&Ipv4Addr::new(192, 168, 1, 96)));
@@ -160,4 +299,32 @@ mod tests {
         assert!(!cidr.contains(&Ipv4Addr::new(192, 168, 1, 95)));
         assert!(!cidr.contains(&Ipv4Addr::new(0, 0, 0, 0)));
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_accessors() {
+        let cidr = AccountCidr {
+            name: "Test".into(),
+            cidr: Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 99), 28).into(),
+        };
+
+        assert_eq!(cidr.addr(), Ipv4Addr::new(192, 168, 1, 99));
+        assert_eq!(cidr.network(), Ipv4Addr::new(192, 168, 1, 96));
+        assert_eq!(cidr.broadcast(), Ipv4Addr::new(192, 168, 1, 111));
+        assert_eq!(cidr.netmask(), Ipv4Addr::new(255, 255, 255, 240));
+        assert_eq!(cidr.hostmask(), Ipv4Addr::new(0, 0, 0, 15));
+        assert_eq!(cidr.trunc(), "192.168.1.96/28".parse::<IpNet>().unwrap());
+    }
+
+    #[test]
+    fn ip_cidr_check_v6() {
+        let cidr = AccountCidr {
+            name: "Test".into(),
+            cidr: "2001:db8::/32".parse().unwrap(),
+        };
+
+        assert!(cidr.contains(&"2001:db8::1".parse::<Ipv6Addr>().unwrap()));
+        assert!(!cidr.contains(&"2001:db9::1".parse::<Ipv6Addr>().unwrap()));
+        assert!(!cidr.contains(&Ipv4Addr::new(192, 168, 1, 96)));
+    }
 }
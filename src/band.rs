@@ -6,6 +6,8 @@ use time::OffsetDateTime;
 use url::Url;
 
 use crate::Hateoas;
+use crate::links::LinkRel;
+use crate::site::SiteConfiguration;
 
 #[cfg(feature = "serde")]
 use super::utils;
@@ -41,6 +43,16 @@ pub enum IoHardware {
     Other(String),
 }
 
+impl IoHardware {
+    /// The name of the hardware as it appears in the API, including for [`IoHardware::Other`]
+    pub fn as_str(&self) -> &str {
+        match self {
+            IoHardware::Other(other) => other.as_str(),
+            known => known.as_ref(),
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -98,4 +110,257 @@ impl Hateoas for Band {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
+}
+
+impl Band {
+    /// The lowest frequency covered by the band, in MHz
+    pub fn lower_edge_mghz(&self) -> f64 {
+        self.frequency_mghz - self.default_band_width_mghz / 2.0
+    }
+
+    /// The highest frequency covered by the band, in MHz
+    pub fn upper_edge_mghz(&self) -> f64 {
+        self.frequency_mghz + self.default_band_width_mghz / 2.0
+    }
+
+    /// The gap between the edges of two bands, in MHz
+    ///
+    /// A negative value indicates the bands overlap by that amount.
+    pub fn separation_mghz(&self, other: &Band) -> f64 {
+        let low = self.upper_edge_mghz().min(other.upper_edge_mghz());
+        let high = self.lower_edge_mghz().max(other.lower_edge_mghz());
+        high - low
+    }
+
+    /// Whether the frequency ranges of the two bands overlap
+    pub fn overlaps(&self, other: &Band) -> bool {
+        self.separation_mghz(other) < 0.0
+    }
+}
+
+/// A problem found when checking a list of bands against each other and a site configuration
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum BandIssue<'a> {
+    /// The frequency ranges of two bands overlap
+    Overlap { first: &'a Band, second: &'a Band },
+    /// A transmit band and a receive band are closer than the required separation
+    TransmitReceiveSeparation {
+        transmit: &'a Band,
+        receive: &'a Band,
+        separation_mghz: f64,
+    },
+    /// The IO hardware of the band is not present in the site configuration
+    MissingHardware {
+        band: &'a Band,
+        hardware: &'a IoHardware,
+    },
+}
+
+impl std::fmt::Display for BandIssue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BandIssue::Overlap { first, second } => write!(
+                f,
+                "Band '{}' overlaps band '{}' by {} MHz",
+                first.name,
+                second.name,
+                -first.separation_mghz(second)
+            ),
+            BandIssue::TransmitReceiveSeparation {
+                transmit,
+                receive,
+                separation_mghz,
+            } => write!(
+                f,
+                "Transmit band '{}' is only {separation_mghz} MHz from receive band '{}'",
+                transmit.name, receive.name
+            ),
+            BandIssue::MissingHardware { band, hardware } => write!(
+                f,
+                "Band '{}' requires {} hardware, which the site configuration does not have",
+                band.name,
+                hardware.as_str()
+            ),
+        }
+    }
+}
+
+/// Finds every pair of bands whose frequency ranges overlap
+pub fn find_overlaps(bands: &[Band]) -> Vec<BandIssue<'_>> {
+    let mut issues = Vec::new();
+    for (idx, first) in bands.iter().enumerate() {
+        for second in &bands[idx + 1..] {
+            if first.overlaps(second) {
+                issues.push(BandIssue::Overlap { first, second });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Finds every transmit and receive pair which are separated by less than `min_separation_mghz`
+///
+/// Pairs which overlap outright are reported here as well, with a negative separation.
+pub fn find_close_transmit_receive(bands: &[Band], min_separation_mghz: f64) -> Vec<BandIssue<'_>> {
+    let with_type = |typ: BandType| bands.iter().filter(move |band| band.typ == Some(typ));
+
+    let mut issues = Vec::new();
+    for transmit in with_type(BandType::Transmit) {
+        for receive in with_type(BandType::Receive) {
+            let separation_mghz = transmit.separation_mghz(receive);
+            if separation_mghz < min_separation_mghz {
+                issues.push(BandIssue::TransmitReceiveSeparation {
+                    transmit,
+                    receive,
+                    separation_mghz,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Finds every band whose IO hardware is not present in the hardware of the site configuration
+///
+/// Bands without IO hardware are not reported. Hardware types are compared case-insensitively.
+pub fn find_missing_hardware<'a>(
+    bands: &'a [Band],
+    configuration: &SiteConfiguration,
+) -> Vec<BandIssue<'a>> {
+    let hardware = configuration.hardware.as_deref().unwrap_or_default();
+
+    bands
+        .iter()
+        .filter_map(|band| {
+            let required = band.io_configuration.io_hardware.as_ref()?;
+            let present = hardware.iter().any(|hw| {
+                hw.typ
+                    .as_ref()
+                    .is_some_and(|typ| typ.as_str().eq_ignore_ascii_case(required.as_str()))
+            });
+
+            (!present).then_some(BandIssue::MissingHardware {
+                band,
+                hardware: required,
+            })
+        })
+        .collect()
+}
+
+/// Runs every band check, returning all issues found
+pub fn check_bands<'a>(
+    bands: &'a [Band],
+    configuration: &SiteConfiguration,
+    min_transmit_receive_separation_mghz: f64,
+) -> Vec<BandIssue<'a>> {
+    let mut issues = find_overlaps(bands);
+    issues.extend(find_close_transmit_receive(
+        bands,
+        min_transmit_receive_separation_mghz,
+    ));
+    issues.extend(find_missing_hardware(bands, configuration));
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+    use crate::site::SiteHardware;
+
+    use super::*;
+
+    fn band(name: &str, typ: BandType, frequency_mghz: f64, width: f64) -> Band {
+        let mut band = fixtures::band(name);
+        band.typ = Some(typ);
+        band.frequency_mghz = frequency_mghz;
+        band.default_band_width_mghz = width;
+        band.io_configuration.io_hardware = Some(IoHardware::Modem);
+        band
+    }
+
+    fn configuration(hardware: &[&str]) -> SiteConfiguration {
+        SiteConfiguration {
+            hardware: Some(
+                hardware
+                    .iter()
+                    .map(|typ| SiteHardware {
+                        typ: Some(typ.parse().unwrap()),
+                        ..fixtures::site_hardware(typ)
+                    })
+                    .collect(),
+            ),
+            ..fixtures::site_configuration()
+        }
+    }
+
+    #[test]
+    fn overlapping_bands() {
+        let bands = [
+            band("a", BandType::Receive, 8000.0, 10.0),
+            band("b", BandType::Receive, 8008.0, 10.0),
+            band("c", BandType::Receive, 8020.0, 10.0),
+        ];
+
+        let issues = find_overlaps(&bands);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0],
+            BandIssue::Overlap {
+                first: &bands[0],
+                second: &bands[1]
+            }
+        );
+    }
+
+    #[test]
+    fn adjacent_bands_do_not_overlap() {
+        let first = band("a", BandType::Receive, 8000.0, 10.0);
+        let second = band("b", BandType::Receive, 8010.0, 10.0);
+        assert!(!first.overlaps(&second));
+        assert_eq!(first.separation_mghz(&second), 0.0);
+    }
+
+    #[test]
+    fn close_transmit_receive() {
+        let bands = [
+            band("tx", BandType::Transmit, 2025.0, 2.0),
+            band("rx-near", BandType::Receive, 2030.0, 2.0),
+            band("rx-far", BandType::Receive, 2200.0, 2.0),
+        ];
+
+        let issues = find_close_transmit_receive(&bands, 50.0);
+        assert_eq!(
+            issues,
+            vec![BandIssue::TransmitReceiveSeparation {
+                transmit: &bands[0],
+                receive: &bands[1],
+                separation_mghz: 3.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_hardware() {
+        let mut recorder = band("rec", BandType::Receive, 8000.0, 10.0);
+        recorder.io_configuration.io_hardware = Some(IoHardware::Recorder);
+        let bands = [band("modem", BandType::Receive, 2000.0, 10.0), recorder];
+
+        let issues = find_missing_hardware(&bands, &configuration(&["modem", "FEP"]));
+        assert_eq!(
+            issues,
+            vec![BandIssue::MissingHardware {
+                band: &bands[1],
+                hardware: &IoHardware::Recorder,
+            }]
+        );
+
+        assert!(check_bands(&bands, &configuration(&["MODEM", "RECORDER"]), 10.0).is_empty());
+    }
 }
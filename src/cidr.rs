@@ -0,0 +1,247 @@
+//! # CIDR
+//!
+//! Set operations over the CIDR whitelists of an [`Account`].
+
+use std::net::IpAddr;
+
+use ipnet::IpNet;
+
+use crate::account::{Account, AccountCidr};
+
+/// A whitelist of CIDR ranges, such as [`Account::access_api_cidr`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CidrSet {
+    entries: Vec<AccountCidr>,
+}
+
+/// An entry of a [`CidrSet`] which adds no coverage, because another entry already covers it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum CidrRedundancy<'a> {
+    /// Both entries cover exactly the same range
+    Duplicate {
+        entry: &'a AccountCidr,
+        duplicate_of: &'a AccountCidr,
+    },
+    /// The entry is a subnet of a larger entry
+    Contained {
+        entry: &'a AccountCidr,
+        covered_by: &'a AccountCidr,
+    },
+}
+
+/// The difference in address coverage between two [`CidrSet`]s
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct CidrDiff {
+    /// Ranges allowed by the new set, but not the old
+    pub added: Vec<IpNet>,
+    /// Ranges allowed by the old set, but not the new
+    pub removed: Vec<IpNet>,
+}
+
+impl CidrDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl CidrSet {
+    pub fn new(entries: Vec<AccountCidr>) -> Self {
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[AccountCidr] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether any range in the set contains the address
+    pub fn allows(&self, addr: impl Into<IpAddr>) -> bool {
+        let addr = addr.into();
+        self.entries.iter().any(|entry| entry.contains(&addr))
+    }
+
+    /// Every entry which contains the address
+    pub fn matching(&self, addr: impl Into<IpAddr>) -> impl Iterator<Item = &AccountCidr> {
+        let addr = addr.into();
+        self.entries
+            .iter()
+            .filter(move |entry| entry.contains(&addr))
+    }
+
+    /// The minimal list of ranges covering exactly the addresses allowed by the set
+    ///
+    /// Adjacent and overlapping entries are merged, IPv4 ranges are listed before IPv6 ranges.
+    pub fn aggregate(&self) -> Vec<IpNet> {
+        let nets = self
+            .entries
+            .iter()
+            .map(|entry| entry.cidr.trunc())
+            .collect();
+        IpNet::aggregate(&nets)
+    }
+
+    /// Entries which are duplicates of, or contained within, another entry
+    ///
+    /// Two CIDR ranges either nest or are disjoint, so this reports every overlap in the set.
+    /// For duplicates, only the later entry is reported.
+    pub fn redundancies(&self) -> Vec<CidrRedundancy<'_>> {
+        let mut redundancies = Vec::new();
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let net = entry.cidr.trunc();
+            for (other_idx, other) in self.entries.iter().enumerate() {
+                let other_net = other.cidr.trunc();
+                if idx == other_idx || !other_net.contains(&net) {
+                    continue;
+                }
+
+                if other_net != net {
+                    redundancies.push(CidrRedundancy::Contained {
+                        entry,
+                        covered_by: other,
+                    });
+                    break;
+                } else if other_idx < idx {
+                    redundancies.push(CidrRedundancy::Duplicate {
+                        entry,
+                        duplicate_of: other,
+                    });
+                    break;
+                }
+            }
+        }
+
+        redundancies
+    }
+
+    /// The change in coverage going from this set to `other`
+    pub fn diff(&self, other: &CidrSet) -> CidrDiff {
+        let old = self.aggregate();
+        let new = other.aggregate();
+
+        CidrDiff {
+            added: subtract_all(&new, &old),
+            removed: subtract_all(&old, &new),
+        }
+    }
+}
+
+/// The ranges of `from` which are not covered by any range of `remove`
+fn subtract_all(from: &[IpNet], remove: &[IpNet]) -> Vec<IpNet> {
+    let mut remaining = from.to_vec();
+    for removed in remove {
+        remaining = remaining
+            .into_iter()
+            .flat_map(|net| subtract(net, *removed))
+            .collect();
+    }
+
+    IpNet::aggregate(&remaining)
+}
+
+fn subtract(net: IpNet, removed: IpNet) -> Vec<IpNet> {
+    if removed.contains(&net) {
+        return Vec::new();
+    }
+    if !net.contains(&removed) {
+        return vec![net];
+    }
+
+    // The removed range is strictly smaller, so split in half and keep whatever is left over
+    net.subnets(net.prefix_len() + 1)
+        .map(|halves| halves.flat_map(|half| subtract(half, removed)).collect())
+        .unwrap_or_default()
+}
+
+impl FromIterator<AccountCidr> for CidrSet {
+    fn from_iter<T: IntoIterator<Item = AccountCidr>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl Account {
+    /// The whitelist for connecting to the Freedom API
+    pub fn api_cidr_set(&self) -> CidrSet {
+        CidrSet::new(self.access_api_cidr.clone())
+    }
+
+    /// The whitelist for connecting to the realtime FPS
+    pub fn realtime_cidr_set(&self) -> CidrSet {
+        CidrSet::new(self.access_realtime_cidr.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn set(cidrs: &[&str]) -> CidrSet {
+        cidrs
+            .iter()
+            .map(|cidr| AccountCidr {
+                name: cidr.to_string(),
+                cidr: cidr.parse().unwrap(),
+            })
+            .collect()
+    }
+
+    fn nets(cidrs: &[&str]) -> Vec<IpNet> {
+        cidrs.iter().map(|cidr| cidr.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn allows() {
+        let set = set(&["10.0.0.0/24", "2001:db8::/32"]);
+        assert!(set.allows(Ipv4Addr::new(10, 0, 0, 12)));
+        assert!(set.allows("2001:db8::1".parse::<IpAddr>().unwrap()));
+        assert!(!set.allows(Ipv4Addr::new(10, 0, 1, 12)));
+        assert_eq!(set.matching(Ipv4Addr::new(10, 0, 0, 1)).count(), 1);
+    }
+
+    #[test]
+    fn aggregate_adjacent_and_overlapping() {
+        let set = set(&[
+            "10.0.0.0/25",
+            "10.0.0.128/25",
+            "10.0.0.64/26",
+            "10.0.2.0/24",
+        ]);
+        assert_eq!(set.aggregate(), nets(&["10.0.0.0/24", "10.0.2.0/24"]));
+    }
+
+    #[test]
+    fn redundant_entries() {
+        let set = set(&["10.0.0.0/24", "10.0.0.64/26", "10.0.0.0/24", "10.1.0.0/24"]);
+        let redundancies = set.redundancies();
+        assert_eq!(
+            redundancies,
+            vec![
+                CidrRedundancy::Contained {
+                    entry: &set.entries()[1],
+                    covered_by: &set.entries()[0],
+                },
+                CidrRedundancy::Duplicate {
+                    entry: &set.entries()[2],
+                    duplicate_of: &set.entries()[0],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_sets() {
+        let old = set(&["10.0.0.0/24", "192.168.0.0/24"]);
+        let new = set(&["10.0.0.0/25", "172.16.0.0/16"]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, nets(&["172.16.0.0/16"]));
+        assert_eq!(diff.removed, nets(&["10.0.0.128/25", "192.168.0.0/24"]));
+        assert!(old.diff(&old).is_empty());
+    }
+}
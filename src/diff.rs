@@ -0,0 +1,743 @@
+//! # Diff
+//!
+//! Field-level comparison between two versions of a model, for auditing what changed when a
+//! resource's `modified` timestamp moves.
+//!
+//! The `modified` field itself is not reported, as it changes on every update. Entries of maps
+//! such as `properties` and `meta_data` are compared per key, and lists such as `hardware` are
+//! matched up by name rather than position.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Debug;
+
+use crate::{
+    account::{Account, AccountCidr, Tier},
+    band::{Band, IoConfiguration},
+    satellite::Satellite,
+    satellite_configuration::SatelliteConfiguration,
+    site::{Site, SiteConfiguration, SiteHardware},
+    task_override::Override,
+};
+
+/// A single change between two versions of a model
+///
+/// Values are rendered with their `Debug` representation, so secrets stay redacted.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum Change {
+    Added {
+        path: String,
+        new: String,
+    },
+    Removed {
+        path: String,
+        old: String,
+    },
+    Modified {
+        path: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl Change {
+    /// The path of the field which changed, such as `properties["my.prop"]`
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Modified { path, .. } => path,
+        }
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Added { path, new } => write!(f, "{path}: added {new}"),
+            Change::Removed { path, old } => write!(f, "{path}: removed {old}"),
+            Change::Modified { path, old, new } => write!(f, "{path}: {old} -> {new}"),
+        }
+    }
+}
+
+/// A model which can report field-level changes against another version of itself
+pub trait Diff {
+    /// The changes going from `self` to `new`
+    fn diff(&self, new: &Self) -> Vec<Change>;
+}
+
+/// Accumulates changes, prefixing each path with the location of a nested model
+#[derive(Default)]
+struct Differ {
+    prefix: String,
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn path(&self, name: &str) -> String {
+        match self.prefix.is_empty() {
+            true => name.to_owned(),
+            false => format!("{}.{name}", self.prefix),
+        }
+    }
+
+    fn field<T: PartialEq + Debug>(&mut self, name: &str, old: &T, new: &T) {
+        if old != new {
+            self.changes.push(Change::Modified {
+                path: self.path(name),
+                old: format!("{old:?}"),
+                new: format!("{new:?}"),
+            });
+        }
+    }
+
+    fn map<V: PartialEq + Debug>(
+        &mut self,
+        name: &str,
+        old: Option<&HashMap<String, V>>,
+        new: Option<&HashMap<String, V>>,
+    ) {
+        let empty = HashMap::new();
+        let old = old.unwrap_or(&empty);
+        let new = new.unwrap_or(&empty);
+        let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+        for key in keys {
+            let path = self.path(&format!("{name}[{key:?}]"));
+            match (old.get(key), new.get(key)) {
+                (Some(old), Some(new)) if old != new => self.changes.push(Change::Modified {
+                    path,
+                    old: format!("{old:?}"),
+                    new: format!("{new:?}"),
+                }),
+                (Some(old), None) => self.changes.push(Change::Removed {
+                    path,
+                    old: format!("{old:?}"),
+                }),
+                (None, Some(new)) => self.changes.push(Change::Added {
+                    path,
+                    new: format!("{new:?}"),
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    /// Compares lists by matching items with the same key, items sharing a key are matched in
+    /// order
+    fn list<T, K>(&mut self, name: &str, old: &[T], new: &[T], key: impl Fn(&T) -> K)
+    where
+        T: Diff + Debug,
+        K: Ord + Debug,
+    {
+        let mut grouped: BTreeMap<K, (Vec<&T>, Vec<&T>)> = BTreeMap::new();
+        for item in old {
+            grouped.entry(key(item)).or_default().0.push(item);
+        }
+        for item in new {
+            grouped.entry(key(item)).or_default().1.push(item);
+        }
+
+        for (key, (old, new)) in grouped {
+            let len = old.len().max(new.len());
+            for idx in 0..len {
+                let path = match len {
+                    1 => self.path(&format!("{name}[{key:?}]")),
+                    _ => self.path(&format!("{name}[{key:?}#{idx}]")),
+                };
+                match (old.get(idx), new.get(idx)) {
+                    (Some(old), Some(new)) => {
+                        self.changes.extend(
+                            old.diff(new)
+                                .into_iter()
+                                .map(|change| prefix_change(change, &path)),
+                        );
+                    }
+                    (Some(old), None) => self.changes.push(Change::Removed {
+                        path,
+                        old: format!("{old:?}"),
+                    }),
+                    (None, Some(new)) => self.changes.push(Change::Added {
+                        path,
+                        new: format!("{new:?}"),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+    }
+
+    fn nested<T: Diff>(&mut self, name: &str, old: &T, new: &T) {
+        let path = self.path(name);
+        self.changes.extend(
+            old.diff(new)
+                .into_iter()
+                .map(|change| prefix_change(change, &path)),
+        );
+    }
+}
+
+fn prefix_change(change: Change, prefix: &str) -> Change {
+    let prefixed = |path: String| format!("{prefix}.{path}");
+    match change {
+        Change::Added { path, new } => Change::Added {
+            path: prefixed(path),
+            new,
+        },
+        Change::Removed { path, old } => Change::Removed {
+            path: prefixed(path),
+            old,
+        },
+        Change::Modified { path, old, new } => Change::Modified {
+            path: prefixed(path),
+            old,
+            new,
+        },
+    }
+}
+
+impl Diff for Override {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            created,
+            name,
+            properties,
+            satellite_details,
+            configuration_details,
+            links,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("created", created, &new.created);
+        d.field("name", name, &new.name);
+        d.map("properties", Some(properties), Some(&new.properties));
+        d.field(
+            "satellite_details",
+            satellite_details,
+            &new.satellite_details,
+        );
+        d.field(
+            "configuration_details",
+            configuration_details,
+            &new.configuration_details,
+        );
+        d.map("links", Some(links), Some(&new.links));
+        d.changes
+    }
+}
+
+impl Diff for Satellite {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            created,
+            name,
+            description,
+            norad_cat_id,
+            tle,
+            internal_meta_data,
+            account_name,
+            meta_data,
+            links,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("created", created, &new.created);
+        d.field("name", name, &new.name);
+        d.field("description", description, &new.description);
+        d.field("norad_cat_id", norad_cat_id, &new.norad_cat_id);
+        d.field("tle", tle, &new.tle);
+        d.map(
+            "internal_meta_data",
+            internal_meta_data.as_ref(),
+            new.internal_meta_data.as_ref(),
+        );
+        d.field("account_name", account_name, &new.account_name);
+        d.map("meta_data", meta_data.as_ref(), new.meta_data.as_ref());
+        d.map("links", Some(links), Some(&new.links));
+        d.changes
+    }
+}
+
+impl Diff for SatelliteConfiguration {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            created,
+            name,
+            orbit,
+            notes,
+            pull_tle,
+            internal_meta_data,
+            account_name,
+            meta_data,
+            links,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("created", created, &new.created);
+        d.field("name", name, &new.name);
+        d.field("orbit", orbit, &new.orbit);
+        d.field("notes", notes, &new.notes);
+        d.field("pull_tle", pull_tle, &new.pull_tle);
+        d.map(
+            "internal_meta_data",
+            internal_meta_data.as_ref(),
+            new.internal_meta_data.as_ref(),
+        );
+        d.field("account_name", account_name, &new.account_name);
+        d.map("meta_data", meta_data.as_ref(), new.meta_data.as_ref());
+        d.map("links", Some(links), Some(&new.links));
+        d.changes
+    }
+}
+
+impl Diff for Site {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            created,
+            internal_meta_data,
+            name,
+            description,
+            location,
+            base_fps_port,
+            properties,
+            links,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("created", created, &new.created);
+        d.map(
+            "internal_meta_data",
+            internal_meta_data.as_ref(),
+            new.internal_meta_data.as_ref(),
+        );
+        d.field("name", name, &new.name);
+        d.field("description", description, &new.description);
+        d.field("location", location, &new.location);
+        d.field("base_fps_port", base_fps_port, &new.base_fps_port);
+        d.map("properties", properties.as_ref(), new.properties.as_ref());
+        d.map("links", Some(links), Some(&new.links));
+        d.changes
+    }
+}
+
+impl Diff for SiteHardware {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            manual,
+            ip,
+            name,
+            specifications,
+            manufacturer,
+            model,
+            created,
+            typ,
+            port_comms,
+            properties,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("manual", manual, &new.manual);
+        d.field("ip", ip, &new.ip);
+        d.field("name", name, &new.name);
+        d.field("specifications", specifications, &new.specifications);
+        d.field("manufacturer", manufacturer, &new.manufacturer);
+        d.field("model", model, &new.model);
+        d.field("created", created, &new.created);
+        d.field("typ", typ, &new.typ);
+        d.field("port_comms", port_comms, &new.port_comms);
+        d.map("properties", properties.as_ref(), new.properties.as_ref());
+        d.changes
+    }
+}
+
+impl Diff for SiteConfiguration {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            created,
+            internal_meta_data,
+            name,
+            description,
+            configuration_seconds,
+            virtual_cfg,
+            properties,
+            hardware,
+            links,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("created", created, &new.created);
+        d.map(
+            "internal_meta_data",
+            internal_meta_data.as_ref(),
+            new.internal_meta_data.as_ref(),
+        );
+        d.field("name", name, &new.name);
+        d.field("description", description, &new.description);
+        d.field(
+            "configuration_seconds",
+            configuration_seconds,
+            &new.configuration_seconds,
+        );
+        d.field("virtual_cfg", virtual_cfg, &new.virtual_cfg);
+        d.map("properties", properties.as_ref(), new.properties.as_ref());
+        d.list(
+            "hardware",
+            hardware.as_deref().unwrap_or_default(),
+            new.hardware.as_deref().unwrap_or_default(),
+            |hardware| hardware.name.clone(),
+        );
+        d.map("links", Some(links), Some(&new.links));
+        d.changes
+    }
+}
+
+impl Diff for IoConfiguration {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            start_hex_pattern,
+            end_hex_pattern,
+            strip_pattern,
+            io_hardware,
+        } = self;
+        let mut d = Differ::default();
+        d.field(
+            "start_hex_pattern",
+            start_hex_pattern,
+            &new.start_hex_pattern,
+        );
+        d.field("end_hex_pattern", end_hex_pattern, &new.end_hex_pattern);
+        d.field("strip_pattern", strip_pattern, &new.strip_pattern);
+        d.field("io_hardware", io_hardware, &new.io_hardware);
+        d.changes
+    }
+}
+
+impl Diff for Band {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            created,
+            name,
+            typ,
+            frequency_mghz,
+            default_band_width_mghz,
+            io_configuration,
+            manual_transmit_control,
+            account_name,
+            links,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("created", created, &new.created);
+        d.field("name", name, &new.name);
+        d.field("typ", typ, &new.typ);
+        d.field("frequency_mghz", frequency_mghz, &new.frequency_mghz);
+        d.field(
+            "default_band_width_mghz",
+            default_band_width_mghz,
+            &new.default_band_width_mghz,
+        );
+        d.nested("io_configuration", io_configuration, &new.io_configuration);
+        d.field(
+            "manual_transmit_control",
+            manual_transmit_control,
+            &new.manual_transmit_control,
+        );
+        d.field("account_name", account_name, &new.account_name);
+        d.map("links", Some(links), Some(&new.links));
+        d.changes
+    }
+}
+
+impl Diff for AccountCidr {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self { name, cidr } = self;
+        let mut d = Differ::default();
+        d.field("name", name, &new.name);
+        d.field("cidr", cidr, &new.cidr);
+        d.changes
+    }
+}
+
+impl Diff for Tier {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            tier,
+            price,
+            links: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("tier", tier, &new.tier);
+        d.field("price", price, &new.price);
+        d.changes
+    }
+}
+
+impl Diff for Account {
+    fn diff(&self, new: &Self) -> Vec<Change> {
+        let Self {
+            created,
+            internal_meta_data,
+            name,
+            storage_key,
+            storage_group,
+            tiers,
+            post_process_done_by_account,
+            weeks_of_data_storage,
+            verified,
+            access_realtime_cidr,
+            access_api_cidr,
+            external_id,
+            fps_host_name,
+            links,
+            modified: _,
+        } = self;
+        let mut d = Differ::default();
+        d.field("created", created, &new.created);
+        d.map(
+            "internal_meta_data",
+            internal_meta_data.as_ref(),
+            new.internal_meta_data.as_ref(),
+        );
+        d.field("name", name, &new.name);
+        d.field("storage_key", storage_key, &new.storage_key);
+        d.field("storage_group", storage_group, &new.storage_group);
+        d.list("tiers", tiers, &new.tiers, |tier| tier.tier);
+        d.field(
+            "post_process_done_by_account",
+            post_process_done_by_account,
+            &new.post_process_done_by_account,
+        );
+        d.field(
+            "weeks_of_data_storage",
+            weeks_of_data_storage,
+            &new.weeks_of_data_storage,
+        );
+        d.field("verified", verified, &new.verified);
+        d.list(
+            "access_realtime_cidr",
+            access_realtime_cidr,
+            &new.access_realtime_cidr,
+            |cidr| cidr.name.clone(),
+        );
+        d.list(
+            "access_api_cidr",
+            access_api_cidr,
+            &new.access_api_cidr,
+            |cidr| cidr.name.clone(),
+        );
+        d.field("external_id", external_id, &new.external_id);
+        d.field("fps_host_name", fps_host_name, &new.fps_host_name);
+        d.map("links", Some(links), Some(&new.links));
+        d.changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use crate::band::IoHardware;
+    use crate::fixtures;
+
+    use super::*;
+
+    fn cidr(name: &str, cidr: &str) -> AccountCidr {
+        AccountCidr {
+            name: name.into(),
+            cidr: cidr.parse().unwrap(),
+        }
+    }
+
+    fn hardware(name: &str, port: u16) -> SiteHardware {
+        SiteHardware {
+            port_comms: Some(port),
+            ..fixtures::site_hardware(name)
+        }
+    }
+
+    #[test]
+    fn site_configuration_changes() {
+        let old = SiteConfiguration {
+            configuration_seconds: 60,
+            properties: Some(HashMap::from([
+                ("kept".into(), "1".into()),
+                ("changed".into(), "1".into()),
+                ("removed".into(), "1".into()),
+            ])),
+            hardware: Some(vec![hardware("Modem", 5000), hardware("Recorder", 6000)]),
+            ..fixtures::site_configuration()
+        };
+        let new = SiteConfiguration {
+            modified: Some(OffsetDateTime::now_utc()),
+            configuration_seconds: 90,
+            properties: Some(HashMap::from([
+                ("kept".into(), "1".into()),
+                ("changed".into(), "2".into()),
+            ])),
+            hardware: Some(vec![hardware("Modem", 5001), hardware("Antenna", 7000)]),
+            ..old.clone()
+        };
+
+        let changes = old.diff(&new);
+        let paths: Vec<_> = changes.iter().map(Change::path).collect();
+        assert_eq!(
+            paths,
+            [
+                "configuration_seconds",
+                r#"properties["changed"]"#,
+                r#"properties["removed"]"#,
+                r#"hardware["Antenna"]"#,
+                r#"hardware["Modem"].port_comms"#,
+                r#"hardware["Recorder"]"#,
+            ]
+        );
+        assert_eq!(
+            changes[4],
+            Change::Modified {
+                path: r#"hardware["Modem"].port_comms"#.into(),
+                old: "Some(5000)".into(),
+                new: "Some(5001)".into(),
+            }
+        );
+        assert!(matches!(changes[3], Change::Added { .. }));
+        assert!(matches!(changes[5], Change::Removed { .. }));
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn band_nested_changes() {
+        let mut old = fixtures::band("Band");
+        old.io_configuration.io_hardware = Some(IoHardware::Modem);
+        let mut new = old.clone();
+        new.io_configuration.io_hardware = Some(IoHardware::Fep);
+
+        assert_eq!(
+            old.diff(&new),
+            vec![Change::Modified {
+                path: "io_configuration.io_hardware".into(),
+                old: "Some(Modem)".into(),
+                new: "Some(Fep)".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn account_cidr_changes() {
+        let old = Account {
+            access_realtime_cidr: vec![
+                cidr("Office", "192.0.2.0/24"),
+                cidr("Lab", "198.51.100.0/24"),
+            ],
+            access_api_cidr: vec![cidr("Office", "192.0.2.0/24")],
+            ..fixtures::account()
+        };
+        let new = Account {
+            access_realtime_cidr: vec![
+                cidr("Office", "192.0.2.0/25"),
+                cidr("Vpn", "2001:db8::/32"),
+            ],
+            access_api_cidr: Vec::new(),
+            ..old.clone()
+        };
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                Change::Removed {
+                    path: r#"access_realtime_cidr["Lab"]"#.into(),
+                    old: format!("{:?}", old.access_realtime_cidr[1]),
+                },
+                Change::Modified {
+                    path: r#"access_realtime_cidr["Office"].cidr"#.into(),
+                    old: "192.0.2.0/24".into(),
+                    new: "192.0.2.0/25".into(),
+                },
+                Change::Added {
+                    path: r#"access_realtime_cidr["Vpn"]"#.into(),
+                    new: format!("{:?}", new.access_realtime_cidr[1]),
+                },
+                Change::Removed {
+                    path: r#"access_api_cidr["Office"]"#.into(),
+                    old: format!("{:?}", old.access_api_cidr[0]),
+                },
+            ]
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn override_property_changes() {
+        let old = Override {
+            properties: HashMap::from([
+                ("kept".into(), "1".into()),
+                ("changed".into(), "1".into()),
+                ("removed".into(), "1".into()),
+            ]),
+            ..fixtures::task_override()
+        };
+        let mut new = old.clone();
+        new.properties.remove("removed");
+        new.properties.insert("changed".into(), "2".into());
+        new.properties.insert("added".into(), "1".into());
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                Change::Added {
+                    path: r#"properties["added"]"#.into(),
+                    new: r#""1""#.into(),
+                },
+                Change::Modified {
+                    path: r#"properties["changed"]"#.into(),
+                    old: r#""1""#.into(),
+                    new: r#""2""#.into(),
+                },
+                Change::Removed {
+                    path: r#"properties["removed"]"#.into(),
+                    old: r#""1""#.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn satellite_changes() {
+        let old = crate::satellite::Satellite {
+            norad_cat_id: Some(25544),
+            meta_data: Some(HashMap::from([("owner".into(), "Ada".into())])),
+            ..fixtures::satellite()
+        };
+        let new = crate::satellite::Satellite {
+            modified: Some(OffsetDateTime::now_utc()),
+            description: "Station".into(),
+            norad_cat_id: None,
+            meta_data: Some(HashMap::from([("mission".into(), "Relay".into())])),
+            ..old.clone()
+        };
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                Change::Modified {
+                    path: "description".into(),
+                    old: r#""""#.into(),
+                    new: r#""Station""#.into(),
+                },
+                Change::Modified {
+                    path: "norad_cat_id".into(),
+                    old: "Some(25544)".into(),
+                    new: "None".into(),
+                },
+                Change::Added {
+                    path: r#"meta_data["mission"]"#.into(),
+                    new: r#""Relay""#.into(),
+                },
+                Change::Removed {
+                    path: r#"meta_data["owner"]"#.into(),
+                    old: r#""Ada""#.into(),
+                },
+            ]
+        );
+    }
+}
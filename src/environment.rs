@@ -0,0 +1,344 @@
+//! # Environment
+//!
+//! The Freedom deployments, used to resolve relative links and to move links between API bases,
+//! such as when proxying the API through a gateway.
+
+use std::hash::{Hash, Hasher};
+
+use url::Url;
+
+use crate::{Hateoas, error::Error};
+
+/// A deployment of the Freedom API, identified by the base URL of its API
+///
+/// Environments are compared by [`Environment::base`], so a custom base with or without its
+/// trailing slash, or pointing at a known deployment, is the same environment.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum Environment {
+    Production,
+    Test,
+    /// Any other base, such as a local instance or a gateway in front of the API
+    Custom(Url),
+}
+
+impl Environment {
+    const PRODUCTION: &str = "https://api.atlasground.com/api/";
+    const TEST: &str = "https://test-api.atlasground.com/api/";
+
+    /// A custom environment, rooted at the provided base
+    ///
+    /// A trailing slash is added to the path of the base if missing, so that relative links
+    /// resolve below it rather than beside it.
+    pub fn custom(base: Url) -> Self {
+        Self::Custom(with_trailing_slash(base))
+    }
+
+    /// The base of the API, always ending in a slash
+    pub fn base(&self) -> Url {
+        match self {
+            Environment::Production => Url::parse(Self::PRODUCTION).unwrap(),
+            Environment::Test => Url::parse(Self::TEST).unwrap(),
+            Environment::Custom(base) => with_trailing_slash(base.clone()),
+        }
+    }
+
+    /// The known deployment serving the URL, if any
+    pub fn of(url: &Url) -> Option<Self> {
+        [Environment::Production, Environment::Test]
+            .into_iter()
+            .find(|env| env.contains(url))
+    }
+
+    /// Whether the URL is below the base of the environment
+    pub fn contains(&self, url: &Url) -> bool {
+        url.as_str().starts_with(self.base().as_str())
+    }
+
+    /// Resolves a link against the base of the environment
+    ///
+    /// Absolute links are returned unchanged, relative links such as `requests/1` or
+    /// `/api/requests/1` are joined onto the base.
+    pub fn resolve(&self, href: &str) -> Result<Url, Error> {
+        self.base()
+            .join(href)
+            .map_err(|e| Error::Link(format!("{href}: {e}")))
+    }
+
+    /// Moves the URL from the base of this environment to the base of another
+    ///
+    /// Returns `None` when the URL is not below the base of this environment.
+    pub fn rebase(&self, url: &Url, to: &Environment) -> Option<Url> {
+        let from = self.base();
+        let rest = url.as_str().strip_prefix(from.as_str())?;
+
+        to.base().join(rest).ok()
+    }
+
+    /// Moves every link of the model below this environment to another, returning the number
+    /// of links rewritten
+    ///
+    /// Links pointing elsewhere are left untouched. The full view of the links, when the model
+    /// keeps one, is rebased as well, including templated links.
+    pub fn rebase_links<M>(&self, model: &mut M, to: &Environment) -> usize
+    where
+        M: Hateoas + ?Sized,
+    {
+        let mut rewritten = 0;
+        for url in model.get_links_mut().values_mut() {
+            if let Some(rebased) = self.rebase(url, to) {
+                *url = rebased;
+                rewritten += 1;
+            }
+        }
+
+        if let Some(full) = model.full_links_mut() {
+            let (from, to) = (self.base(), to.base());
+            for link in full.0.values_mut().flatten() {
+                // Templates aren't valid URLs, so they are moved as strings
+                if let Some(rest) = link.href.strip_prefix(from.as_str()) {
+                    link.href = format!("{to}{rest}");
+                }
+            }
+        }
+
+        rewritten
+    }
+
+    /// Resolves the relative links of the model against the base of the environment, returning
+    /// the number of relations resolved
+    ///
+    /// The simple view of the links only holds absolute URLs, so relative links are only kept
+    /// by the full view, such as the one of [`Linked`](crate::links::Linked). Once resolved,
+    /// the first link of each relation is available through [`Hateoas::link`].
+    pub fn resolve_links<M>(&self, model: &mut M) -> Result<usize, Error>
+    where
+        M: Hateoas + ?Sized,
+    {
+        let resolved = model
+            .full_links()
+            .0
+            .iter()
+            .filter(|(rel, _)| !model.get_links().contains_key(rel.as_str()))
+            .filter_map(|(rel, links)| links.first().map(|link| (rel, link)))
+            .filter(|(_, link)| link.is_relative())
+            .map(|(rel, link)| {
+                let href = link.expand_href(std::iter::empty::<(&str, &str)>());
+                Ok((rel.clone(), self.resolve(&href)?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let count = resolved.len();
+        model.get_links_mut().extend(resolved);
+        Ok(count)
+    }
+}
+
+fn with_trailing_slash(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+
+    url
+}
+
+impl PartialEq for Environment {
+    fn eq(&self, other: &Self) -> bool {
+        self.base() == other.base()
+    }
+}
+
+impl Eq for Environment {}
+
+impl Hash for Environment {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base().hash(state);
+    }
+}
+
+impl From<Url> for Environment {
+    fn from(base: Url) -> Self {
+        Self::custom(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    struct Model {
+        links: HashMap<String, Url>,
+    }
+
+    impl Hateoas for Model {
+        fn get_links(&self) -> &HashMap<String, Url> {
+            &self.links
+        }
+
+        fn get_links_mut(&mut self) -> &mut HashMap<String, Url> {
+            &mut self.links
+        }
+    }
+
+    fn gateway() -> Environment {
+        Environment::custom(Url::parse("https://gateway.internal/freedom/api").unwrap())
+    }
+
+    #[test]
+    fn custom_base() {
+        assert_eq!(
+            gateway().base().as_str(),
+            "https://gateway.internal/freedom/api/"
+        );
+        assert_eq!(
+            Environment::Custom(Url::parse("http://localhost:8080/api").unwrap())
+                .base()
+                .as_str(),
+            "http://localhost:8080/api/"
+        );
+    }
+
+    #[test]
+    fn equal_bases() {
+        let local = |base| Environment::Custom(Url::parse(base).unwrap());
+        assert_eq!(
+            local("http://localhost:8080/api"),
+            Environment::custom(Url::parse("http://localhost:8080/api/").unwrap())
+        );
+        assert_eq!(
+            local("https://api.atlasground.com/api"),
+            Environment::Production
+        );
+        assert_ne!(
+            local("http://localhost:8080/api"),
+            local("http://localhost:8080")
+        );
+
+        let environments = std::collections::HashSet::from([
+            local("http://localhost:8080/api"),
+            local("http://localhost:8080/api/"),
+            Environment::Test,
+        ]);
+        assert_eq!(environments.len(), 2);
+    }
+
+    #[test]
+    fn known_environment() {
+        let url = Url::parse("https://test-api.atlasground.com/api/requests/1").unwrap();
+        assert_eq!(Environment::of(&url), Some(Environment::Test));
+
+        let url = Url::parse("https://api.atlasground.com/api/requests/1").unwrap();
+        assert_eq!(Environment::of(&url), Some(Environment::Production));
+
+        let url = Url::parse("http://localhost:8080/api/requests/1").unwrap();
+        assert_eq!(Environment::of(&url), None);
+    }
+
+    #[test]
+    fn resolve() {
+        let env = Environment::Test;
+        assert_eq!(
+            env.resolve("requests/1").unwrap().as_str(),
+            "https://test-api.atlasground.com/api/requests/1"
+        );
+        assert_eq!(
+            env.resolve("/api/sites/1").unwrap().as_str(),
+            "https://test-api.atlasground.com/api/sites/1"
+        );
+        assert_eq!(
+            env.resolve("http://localhost:8080/api/sites/1")
+                .unwrap()
+                .as_str(),
+            "http://localhost:8080/api/sites/1"
+        );
+    }
+
+    #[test]
+    fn rebase_links() {
+        let mut model = Model {
+            links: HashMap::from([
+                (
+                    "self".to_string(),
+                    Url::parse("https://test-api.atlasground.com/api/requests/1?projection=full")
+                        .unwrap(),
+                ),
+                (
+                    "site".to_string(),
+                    Url::parse("https://test-api.atlasground.com/api/sites/2").unwrap(),
+                ),
+                (
+                    "profile".to_string(),
+                    Url::parse("https://example.com/profile").unwrap(),
+                ),
+            ]),
+        };
+
+        assert_eq!(Environment::Test.rebase_links(&mut model, &gateway()), 2);
+        assert_eq!(
+            model.links["self"].as_str(),
+            "https://gateway.internal/freedom/api/requests/1?projection=full"
+        );
+        assert_eq!(
+            model.links["site"].as_str(),
+            "https://gateway.internal/freedom/api/sites/2"
+        );
+        assert_eq!(
+            model.links["profile"].as_str(),
+            "https://example.com/profile"
+        );
+
+        assert_eq!(gateway().rebase_links(&mut model, &Environment::Test), 2);
+        assert_eq!(
+            model.links["site"].as_str(),
+            "https://test-api.atlasground.com/api/sites/2"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn relative_links_round_trip() {
+        use crate::{fixtures, links::Linked, site::Site};
+
+        let links = serde_json::json!({
+            "self": { "href": "/api/sites/1" },
+            "configurations": { "href": "sites/1/configurations{?projection}", "templated": true },
+            "profile": { "href": "https://example.com/profile" }
+        });
+        let mut json = serde_json::to_value(fixtures::site()).unwrap();
+        json["_links"] = links.clone();
+
+        let site: Site = serde_json::from_value(json.clone()).unwrap();
+        assert!(site.self_link().is_none());
+        assert_eq!(site.links.len(), 1);
+
+        let mut site: Linked<Site> = serde_json::from_value(json).unwrap();
+        assert_eq!(serde_json::to_value(&site).unwrap()["_links"], links);
+
+        assert_eq!(Environment::Test.resolve_links(&mut site), Ok(2));
+        assert_eq!(
+            site.self_link().unwrap().as_str(),
+            "https://test-api.atlasground.com/api/sites/1"
+        );
+        assert_eq!(
+            serde_json::to_value(&site).unwrap()["_links"]["configurations"],
+            serde_json::json!({
+                "href": "https://test-api.atlasground.com/api/sites/1/configurations"
+            })
+        );
+
+        assert_eq!(Environment::Test.rebase_links(&mut site, &gateway()), 2);
+        assert_eq!(
+            site.full_links()
+                .get_all(&"configurations".parse().unwrap())[0]
+                .href,
+            "sites/1/configurations{?projection}"
+        );
+        assert_eq!(
+            serde_json::to_value(&site).unwrap()["_links"]["self"]["href"],
+            "https://gateway.internal/freedom/api/sites/1"
+        );
+    }
+}
@@ -12,6 +12,16 @@ pub enum Error {
     PaginatedInner,
     PaginatedListMissing,
     Link(String),
+    InvalidHost(String),
+    MissingFpsHost,
+    InvalidFpsPort(i64),
+    UnknownPricingTier(u32),
+    MissingLink(String),
+    InvalidId(String),
+    MissingEmbedded(String),
+    AmbiguousEmbedded(Vec<String>),
+    Embedded(String),
+    InvalidQuery(String),
 }
 
 impl std::fmt::Display for Error {
@@ -23,7 +33,26 @@ impl std::fmt::Display for Error {
             Error::PaginatedListMissing => {
                 f.write_str("The inner list of paginated elements is missing")
             }
-            Error::Link(inner) => write!(f, "The link map failed to deserialize: {inner}"),
+            Error::Link(inner) => write!(f, "The link is invalid: {inner}"),
+            Error::InvalidHost(inner) => write!(f, "Invalid IP address or hostname: {inner}"),
+            Error::MissingFpsHost => f.write_str("The account has no FPS host name"),
+            Error::InvalidFpsPort(port) => write!(
+                f,
+                "The FPS port {port} is out of range or assigned to several bands"
+            ),
+            Error::UnknownPricingTier(tier) => write!(f, "The account has no pricing tier {tier}"),
+            Error::MissingLink(rel) => write!(f, "The model has no '{rel}' link"),
+            Error::InvalidId(url) => write!(f, "The URL does not identify the resource: {url}"),
+            Error::MissingEmbedded(rel) => write!(f, "Nothing is embedded under '{rel}'"),
+            Error::AmbiguousEmbedded(rels) => write!(
+                f,
+                "Expected a single embedded relation, found [{}]",
+                rels.join(", ")
+            ),
+            Error::Embedded(inner) => {
+                write!(f, "The embedded relation failed to deserialize: {inner}")
+            }
+            Error::InvalidQuery(inner) => write!(f, "The query can't be built: {inner}"),
         }
     }
 }
@@ -0,0 +1,233 @@
+//! Minimal models shared by the unit tests
+//!
+//! Tests adjust the fields they care about with struct update syntax, so a new field on a model
+//! only needs to be added here.
+
+use std::collections::HashMap;
+
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+    account::Account,
+    azel::Location,
+    band::{Band, BandType, IoConfiguration},
+    site::{Site, SiteConfiguration, SiteHardware},
+    task::Task,
+    task_override::Override,
+};
+
+pub(crate) fn account() -> Account {
+    Account {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        internal_meta_data: None,
+        name: "Account".into(),
+        storage_key: Default::default(),
+        storage_group: None,
+        tiers: Vec::new(),
+        post_process_done_by_account: false,
+        weeks_of_data_storage: 4,
+        verified: true,
+        access_realtime_cidr: Vec::new(),
+        access_api_cidr: Vec::new(),
+        external_id: None,
+        fps_host_name: None,
+        links: HashMap::new(),
+    }
+}
+
+/// A receive band at 8000 MHz, 10 MHz wide
+pub(crate) fn band(name: &str) -> Band {
+    Band {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        name: name.into(),
+        typ: Some(BandType::Receive),
+        frequency_mghz: 8000.0,
+        default_band_width_mghz: 10.0,
+        io_configuration: IoConfiguration {
+            start_hex_pattern: None,
+            end_hex_pattern: None,
+            strip_pattern: false,
+            io_hardware: None,
+        },
+        manual_transmit_control: None,
+        account_name: None,
+        links: HashMap::new(),
+    }
+}
+
+pub(crate) fn satellite() -> crate::satellite::Satellite {
+    crate::satellite::Satellite {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        name: "Satellite".into(),
+        description: String::new(),
+        norad_cat_id: None,
+        tle: None,
+        internal_meta_data: None,
+        account_name: "Account".into(),
+        meta_data: None,
+        links: HashMap::new(),
+    }
+}
+
+#[cfg(any(feature = "redact", feature = "serde"))]
+pub(crate) fn satellite_configuration() -> crate::satellite_configuration::SatelliteConfiguration {
+    crate::satellite_configuration::SatelliteConfiguration {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        name: "Configuration".into(),
+        orbit: String::new(),
+        notes: String::new(),
+        pull_tle: false,
+        internal_meta_data: None,
+        account_name: "Account".into(),
+        meta_data: None,
+        links: HashMap::new(),
+    }
+}
+
+pub(crate) fn site() -> Site {
+    Site {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        internal_meta_data: None,
+        name: "Site".into(),
+        description: None,
+        location: Location {
+            longitude: 0.0,
+            latitude: 0.0,
+            elevation: 0.0,
+        },
+        base_fps_port: 0,
+        properties: None,
+        links: HashMap::new(),
+    }
+}
+
+pub(crate) fn site_configuration() -> SiteConfiguration {
+    SiteConfiguration {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        internal_meta_data: None,
+        name: "Config".into(),
+        description: None,
+        configuration_seconds: 0,
+        virtual_cfg: None,
+        properties: None,
+        hardware: None,
+        links: HashMap::new(),
+    }
+}
+
+pub(crate) fn site_hardware(name: &str) -> SiteHardware {
+    SiteHardware {
+        manual: false,
+        ip: None,
+        name: name.into(),
+        specifications: None,
+        manufacturer: None,
+        model: None,
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        typ: None,
+        port_comms: None,
+        properties: None,
+    }
+}
+
+/// A billable task starting at `start`, lasting `seconds`
+pub(crate) fn task(start: OffsetDateTime, seconds: u32) -> Task {
+    Task {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        found_visibility: true,
+        internal_meta_data: None,
+        score: None,
+        start,
+        end: start + Duration::seconds(seconds.into()),
+        visibility_start: None,
+        visibility_end: None,
+        billable: true,
+        duration_in_seconds: seconds,
+        task_within_config_window: true,
+        duration: String::new(),
+        file_results: Vec::new(),
+        meta_data: None,
+        links: HashMap::new(),
+    }
+}
+
+/// A received test request, targeting the Unix epoch
+#[cfg(any(feature = "redact", feature = "serde"))]
+pub(crate) fn task_request() -> crate::task::TaskRequest {
+    use crate::task::{TaskRequest, TaskStatus, TaskStatusType, TaskType};
+
+    TaskRequest {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        internal_meta_data: None,
+        task_type: TaskType::Test,
+        hours_of_flex: 0,
+        duration: 600,
+        minimum_duration: 600,
+        target_date: OffsetDateTime::UNIX_EPOCH,
+        earliest_start: OffsetDateTime::UNIX_EPOCH,
+        latest_start: OffsetDateTime::UNIX_EPOCH,
+        transmitting: false,
+        test_file: None,
+        status_changes: Vec::new(),
+        task_active: true,
+        task_request_scheduled: false,
+        task_request_cancelled: false,
+        flex: false,
+        latest_status_change: TaskStatus {
+            created: OffsetDateTime::UNIX_EPOCH,
+            status: TaskStatusType::Received,
+            reason: String::new(),
+        },
+        meta_data: None,
+        links: HashMap::new(),
+    }
+}
+
+pub(crate) fn task_override() -> Override {
+    Override {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        name: "Override".into(),
+        properties: HashMap::new(),
+        satellite_details: None,
+        configuration_details: None,
+        links: HashMap::new(),
+    }
+}
+
+#[cfg(any(feature = "redact", feature = "serde"))]
+pub(crate) fn user() -> crate::user::User {
+    use crate::user::{User, UserPreferences};
+
+    User {
+        created: OffsetDateTime::UNIX_EPOCH,
+        modified: None,
+        first_name: "Ada".into(),
+        last_name: "Lovelace".into(),
+        verified: true,
+        email: "ada@example.com".into(),
+        preferences: UserPreferences {
+            visibility_days: 7,
+            min_elevation: 10.0,
+            max_elevation: 90.0,
+            min_duration: 5.0,
+            elevation_tolerance: 0.0,
+            duration_tolerance: 0.0,
+            notify_via_email: false,
+            notify_via_text: false,
+        },
+        internal_meta_data: None,
+        deleted: None,
+        api_access_enabled: true,
+        links: HashMap::new(),
+    }
+}
@@ -3,7 +3,121 @@
 //! Contains models for interacting with Freedom Gateway licensing endpoints.
 
 use strum::{AsRefStr, EnumString};
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
+
+use crate::secret::Secret;
+
+/// Request body for creating a new license.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct CreateRequest {
+    #[cfg_attr(feature = "serde", serde(with = "time::serde::iso8601"))]
+    pub expires_at: OffsetDateTime,
+}
+
+impl CreateRequest {
+    pub fn new(expires_at: OffsetDateTime) -> Self {
+        Self { expires_at }
+    }
+}
+
+/// Request body for regenerating the key of an existing license.
+///
+/// When `expires_at` is omitted, the license keeps its current expiry.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct RegenerateRequest {
+    pub license_id: u32,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "time::serde::iso8601::option"
+        )
+    )]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl RegenerateRequest {
+    pub fn new(license_id: u32) -> Self {
+        Self {
+            license_id,
+            expires_at: None,
+        }
+    }
+
+    pub fn with_expires_at(mut self, expires_at: OffsetDateTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+}
+
+/// Request body for verifying a license key.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct VerifyRequest {
+    pub license_key: Secret,
+}
+
+impl VerifyRequest {
+    pub fn new(license_key: impl Into<Secret>) -> Self {
+        Self {
+            license_key: license_key.into(),
+        }
+    }
+}
+
+/// Request body for activating an inactive license.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct ActivateRequest {
+    pub license_id: u32,
+}
+
+impl ActivateRequest {
+    pub fn new(license_id: u32) -> Self {
+        Self { license_id }
+    }
+}
+
+/// Request body for deactivating an active license.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct DeactivateRequest {
+    pub license_id: u32,
+}
+
+impl DeactivateRequest {
+    pub fn new(license_id: u32) -> Self {
+        Self { license_id }
+    }
+}
 
 /// Response body returned when regenerating a license key.
 #[cfg_attr(
@@ -18,7 +132,7 @@ pub struct RegenerateResponse {
     pub license_id: u32,
     #[cfg_attr(feature = "serde", serde(with = "time::serde::iso8601"))]
     pub expires_at: OffsetDateTime,
-    pub license_key: String,
+    pub license_key: Secret,
 }
 
 /// Response body returned from a license verification request.
@@ -37,7 +151,31 @@ pub struct VerifyResponse {
         serde(default, with = "time::serde::iso8601::option")
     )]
     pub expires_at: Option<OffsetDateTime>,
-    pub reason: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reason: Option<VerifyReason>,
+}
+
+/// The reason a license failed verification.
+///
+/// Reasons not known to this crate are kept as [`VerifyReason::Other`].
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "SCREAMING_SNAKE_CASE")
+)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, AsRefStr, EnumString)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum VerifyReason {
+    /// The key does not match any license.
+    Invalid,
+    /// The license has expired.
+    Expired,
+    /// The license has been deactivated.
+    Inactive,
+    #[strum(default)]
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Other(String),
 }
 
 /// Response body for viewing all licenses associated with an account.
@@ -53,6 +191,99 @@ pub struct VerifyResponse {
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
 pub struct View(pub Vec<ViewOne>);
 
+impl View {
+    /// The active license which expires last.
+    pub fn latest_active(&self) -> Option<&ViewOne> {
+        self.0
+            .iter()
+            .filter(|license| license.status == Status::Active)
+            .max_by_key(|license| license.expires_at)
+    }
+
+    /// Licenses which have not yet expired, but will within `horizon` of `now`, soonest first.
+    pub fn expiring_within(&self, now: OffsetDateTime, horizon: Duration) -> Vec<&ViewOne> {
+        let cutoff = now.saturating_add(horizon);
+        let mut expiring: Vec<_> = self
+            .0
+            .iter()
+            .filter(|license| license.expires_at > now && license.expires_at <= cutoff)
+            .collect();
+        expiring.sort_by_key(|license| license.expires_at);
+        expiring
+    }
+
+    /// Licenses which have not been used since `cutoff`, including licenses never used.
+    pub fn unused_since(&self, cutoff: OffsetDateTime) -> Vec<&ViewOne> {
+        self.0
+            .iter()
+            .filter(|license| license.last_used_at.is_none_or(|used| used < cutoff))
+            .collect()
+    }
+
+    /// Licenses whose key version is behind the newest key version on the account.
+    pub fn stale_key_versions(&self) -> Vec<&ViewOne> {
+        let Some(newest) = self.0.iter().map(|license| license.key_version).max() else {
+            return Vec::new();
+        };
+
+        self.0
+            .iter()
+            .filter(|license| license.key_version < newest)
+            .collect()
+    }
+
+    /// Recommends which active licenses to regenerate, soonest to expire first.
+    ///
+    /// A license is recommended when it has expired, expires within `horizon` of `now`, or has a
+    /// stale key version. Inactive licenses are never recommended.
+    pub fn rotation_plan(&self, now: OffsetDateTime, horizon: Duration) -> Vec<Rotation<'_>> {
+        let expiring = self.expiring_within(now, horizon);
+        let stale = self.stale_key_versions();
+
+        let mut plan: Vec<_> = self
+            .0
+            .iter()
+            .filter(|license| license.status == Status::Active)
+            .filter_map(|license| {
+                let mut reasons = Vec::new();
+                if license.expires_at <= now {
+                    reasons.push(RotationReason::Expired);
+                }
+                if expiring.contains(&license) {
+                    reasons.push(RotationReason::ExpiringSoon);
+                }
+                if stale.contains(&license) {
+                    reasons.push(RotationReason::StaleKeyVersion);
+                }
+
+                (!reasons.is_empty()).then_some(Rotation { license, reasons })
+            })
+            .collect();
+        plan.sort_by_key(|rotation| rotation.license.expires_at);
+        plan
+    }
+}
+
+/// A license which should be regenerated, and why.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct Rotation<'a> {
+    pub license: &'a ViewOne,
+    pub reasons: Vec<RotationReason>,
+}
+
+/// The reason a license should be regenerated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum RotationReason {
+    /// The license has already expired.
+    Expired,
+    /// The license expires within the planning horizon.
+    ExpiringSoon,
+    /// A newer key version exists on the account.
+    StaleKeyVersion,
+}
+
 /// Representation of a single license associated with an account.
 ///
 /// Used in license listing and detail-view responses.
@@ -208,6 +439,79 @@ mod tests {
         assert_eq!(view, should_be);
     }
 
+    fn license(id: u32, status: Status, expires_day: u8, key_version: u32) -> ViewOne {
+        ViewOne {
+            id,
+            account_id: 1,
+            status,
+            expires_at: datetime!(2025 - 12 - 01 00:00:00).assume_utc()
+                + Duration::days(i64::from(expires_day) - 1),
+            last_used_at: None,
+            created: datetime!(2025 - 01 - 01 00:00:00).assume_utc(),
+            modified: datetime!(2025 - 01 - 01 00:00:00).assume_utc(),
+            key_version,
+        }
+    }
+
+    #[test]
+    fn lifecycle_helpers() {
+        let now = datetime!(2025 - 12 - 10 00:00:00).assume_utc();
+        let mut used = license(2, Status::Active, 12, 2);
+        used.last_used_at = Some(datetime!(2025 - 12 - 09 00:00:00).assume_utc());
+        let view = View(vec![
+            license(1, Status::Active, 5, 1),
+            used,
+            license(3, Status::Active, 30, 2),
+            license(4, Status::Inactive, 31, 1),
+        ]);
+
+        assert_eq!(view.latest_active().unwrap().id, 3);
+        let expiring: Vec<_> = view
+            .expiring_within(now, Duration::days(7))
+            .iter()
+            .map(|license| license.id)
+            .collect();
+        assert_eq!(expiring, [2]);
+        let unused: Vec<_> = view
+            .unused_since(datetime!(2025 - 12 - 01 00:00:00).assume_utc())
+            .iter()
+            .map(|license| license.id)
+            .collect();
+        assert_eq!(unused, [1, 3, 4]);
+        let stale: Vec<_> = view
+            .stale_key_versions()
+            .iter()
+            .map(|license| license.id)
+            .collect();
+        assert_eq!(stale, [1, 4]);
+    }
+
+    #[test]
+    fn rotation_plan() {
+        let now = datetime!(2025 - 12 - 10 00:00:00).assume_utc();
+        let view = View(vec![
+            license(1, Status::Active, 12, 2),
+            license(2, Status::Active, 5, 1),
+            license(3, Status::Active, 30, 2),
+            license(4, Status::Inactive, 1, 1),
+        ]);
+
+        let plan = view.rotation_plan(now, Duration::days(7));
+        assert_eq!(
+            plan,
+            vec![
+                Rotation {
+                    license: &view.0[1],
+                    reasons: vec![RotationReason::Expired, RotationReason::StaleKeyVersion],
+                },
+                Rotation {
+                    license: &view.0[0],
+                    reasons: vec![RotationReason::ExpiringSoon],
+                },
+            ]
+        );
+    }
+
     #[test]
     fn regenerate_response() {
         let json = r#"{
@@ -221,7 +525,7 @@ mod tests {
             account_id: 1,
             license_id: 1,
             expires_at: datetime!(2025 - 12 - 11 00:00:00).assume_utc(),
-            license_key: String::from("foobar"),
+            license_key: Secret::new("foobar"),
         };
         assert_eq!(regenerate, should_be);
     }
@@ -254,8 +558,48 @@ mod tests {
             valid: false,
             license_id: None,
             expires_at: None,
-            reason: Some(String::from("INVALID")),
+            reason: Some(VerifyReason::Invalid),
         };
         assert_eq!(verify, should_be);
     }
+
+    #[test]
+    fn verify_response_unknown_reason() {
+        let json = r#"{
+    "valid": false,
+    "reason": "REVOKED"
+}"#;
+        let verify: VerifyResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(verify.reason, Some(VerifyReason::Other("REVOKED".into())));
+    }
+
+    #[test]
+    fn request_bodies() {
+        let create = CreateRequest::new(datetime!(2025 - 12 - 11 00:00:00).assume_utc());
+        assert_eq!(
+            serde_json::to_value(&create).unwrap(),
+            serde_json::json!({ "expiresAt": "+002025-12-11T00:00:00.000000000Z" })
+        );
+
+        let regenerate = RegenerateRequest::new(1);
+        assert_eq!(
+            serde_json::to_value(&regenerate).unwrap(),
+            serde_json::json!({ "licenseId": 1 })
+        );
+
+        let verify = VerifyRequest::new("foobar");
+        assert_eq!(
+            serde_json::to_value(&verify).unwrap(),
+            serde_json::json!({ "licenseKey": "foobar" })
+        );
+
+        assert_eq!(
+            serde_json::to_value(ActivateRequest::new(2)).unwrap(),
+            serde_json::json!({ "licenseId": 2 })
+        );
+        assert_eq!(
+            serde_json::to_value(DeactivateRequest::new(2)).unwrap(),
+            serde_json::json!({ "licenseId": 2 })
+        );
+    }
 }
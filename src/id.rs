@@ -0,0 +1,422 @@
+//! # IDs
+//!
+//! Typed identifiers for Freedom resources, extracted from the `self` link of a model.
+
+use std::{cmp::Ordering, fmt, hash::Hash, marker::PhantomData};
+
+use url::Url;
+
+use crate::{
+    Hateoas,
+    account::Account,
+    band::Band,
+    error::Error,
+    links::LinkRel,
+    satellite::Satellite,
+    satellite_configuration::SatelliteConfiguration,
+    site::{Site, SiteConfiguration},
+    task::{Task, TaskRequest},
+    task_override::Override,
+    user::User,
+};
+
+pub type AccountId = Id<Account>;
+pub type BandId = Id<Band>;
+pub type OverrideId = Id<Override>;
+pub type SatelliteConfigurationId = Id<SatelliteConfiguration>;
+pub type SatelliteId = Id<Satellite>;
+pub type SiteConfigurationId = Id<SiteConfiguration>;
+pub type SiteId = Id<Site>;
+pub type TaskId = Id<Task>;
+pub type TaskRequestId = Id<TaskRequest>;
+pub type UserId = Id<User>;
+
+/// A model which is stored by Freedom, and addressable at `/api/<collection>/<id>`
+pub trait Resource: Hateoas + Sized {
+    /// The path segment of the collection holding the resource, e.g. `requests`
+    const COLLECTION: &'static str;
+
+    /// The ID of the resource, taken from its `self` link
+    fn id(&self) -> Result<Id<Self>, Error> {
+        self.uri().map(|uri| uri.id())
+    }
+
+    /// The typed URI of the resource, taken from its `self` link
+    fn uri(&self) -> Result<ResourceUri<Self>, Error> {
+        ResourceUri::new(self.require_link(&LinkRel::SelfLink)?.clone())
+    }
+}
+
+macro_rules! impl_resource {
+    ($($model:ty => $collection:literal),* $(,)?) => {
+        $(
+            impl Resource for $model {
+                const COLLECTION: &'static str = $collection;
+            }
+        )*
+    };
+}
+
+impl_resource!(
+    Account => "accounts",
+    Band => "satellite_bands",
+    Override => "overrides",
+    Satellite => "satellites",
+    SatelliteConfiguration => "satellite_configurations",
+    Site => "sites",
+    SiteConfiguration => "configurations",
+    Task => "tasks",
+    TaskRequest => "requests",
+    User => "users",
+);
+
+/// The ID of a resource of type `T`
+///
+/// IDs of different resources are distinct types, so a [`TaskId`] can't be used where a
+/// [`TaskRequestId`] is expected.
+pub struct Id<T> {
+    value: i32,
+    model: PhantomData<fn() -> T>,
+}
+
+impl<T> Id<T> {
+    pub const fn new(value: i32) -> Self {
+        Self {
+            value,
+            model: PhantomData,
+        }
+    }
+
+    pub const fn get(&self) -> i32 {
+        self.value
+    }
+}
+
+impl<T: Resource> Id<T> {
+    /// Extracts the ID from the URL of a resource, such as `/api/requests/190029`
+    ///
+    /// The segment preceding the ID must match [`Resource::COLLECTION`].
+    pub fn from_url(url: &Url) -> Result<Self, Error> {
+        let invalid = || Error::InvalidId(url.to_string());
+
+        let mut segments = url
+            .path_segments()
+            .ok_or_else(invalid)?
+            .rev()
+            .skip_while(|segment| segment.is_empty());
+        let id = segments.next().ok_or_else(invalid)?;
+        let collection = segments.next().ok_or_else(invalid)?;
+        if collection != T::COLLECTION {
+            return Err(invalid());
+        }
+
+        id.parse().map(Self::new).map_err(|_| invalid())
+    }
+}
+
+impl<T> From<i32> for Id<T> {
+    fn from(value: i32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<Id<T>> for i32 {
+    fn from(id: Id<T>) -> Self {
+        id.value
+    }
+}
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let model = std::any::type_name::<T>()
+            .rsplit("::")
+            .next()
+            .unwrap_or_default();
+        write!(f, "Id<{model}>({})", self.value)
+    }
+}
+
+impl<T> fmt::Display for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Id<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Id<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        i32::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// The URI of a resource of type `T`, which is known to hold a valid ID
+///
+/// This is the typed form of both URIs held as plain fields, such as
+/// [`SiteMetric::site_uri`](crate::status::SiteMetric::site_uri), and of HATEOAS links, see
+/// [`Hateoas::resource_link`], so models can be joined without comparing strings.
+pub struct ResourceUri<T> {
+    url: Url,
+    id: Id<T>,
+}
+
+impl<T: Resource> ResourceUri<T> {
+    pub fn new(url: Url) -> Result<Self, Error> {
+        let id = Id::from_url(&url)?;
+
+        Ok(Self { url, id })
+    }
+}
+
+impl<T> ResourceUri<T> {
+    pub fn id(&self) -> Id<T> {
+        self.id
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn into_url(self) -> Url {
+        self.url
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.url.as_str()
+    }
+}
+
+impl<T: Resource> std::str::FromStr for ResourceUri<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let url = Url::parse(s).map_err(|e| Error::Link(format!("{s}: {e}")))?;
+
+        Self::new(url)
+    }
+}
+
+impl<T: Resource> TryFrom<Url> for ResourceUri<T> {
+    type Error = Error;
+
+    fn try_from(url: Url) -> Result<Self, Self::Error> {
+        Self::new(url)
+    }
+}
+
+impl<T> From<ResourceUri<T>> for Url {
+    fn from(uri: ResourceUri<T>) -> Self {
+        uri.url
+    }
+}
+
+impl<T> AsRef<Url> for ResourceUri<T> {
+    fn as_ref(&self) -> &Url {
+        &self.url
+    }
+}
+
+impl<T> Clone for ResourceUri<T> {
+    fn clone(&self) -> Self {
+        Self {
+            url: self.url.clone(),
+            id: self.id,
+        }
+    }
+}
+
+impl<T> PartialEq for ResourceUri<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.url == other.url
+    }
+}
+
+impl<T> Eq for ResourceUri<T> {}
+
+impl<T> Hash for ResourceUri<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.url.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for ResourceUri<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceUri")
+            .field("url", &self.url.as_str())
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for ResourceUri<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.url.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for ResourceUri<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.url.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Resource> serde::Deserialize<'de> for ResourceUri<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let url = Url::deserialize(deserializer)?;
+
+        Self::new(url).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn id_from_url() {
+        let url = Url::parse("http://localhost:8080/api/requests/190029").unwrap();
+        assert_eq!(
+            TaskRequestId::from_url(&url),
+            Ok(TaskRequestId::new(190029))
+        );
+
+        let url = Url::parse("http://localhost:8080/api/requests/190029/").unwrap();
+        assert_eq!(
+            TaskRequestId::from_url(&url),
+            Ok(TaskRequestId::new(190029))
+        );
+
+        let url = Url::parse("http://localhost:8080/api/tasks/190029").unwrap();
+        assert_eq!(
+            TaskRequestId::from_url(&url),
+            Err(Error::InvalidId(url.to_string()))
+        );
+        assert!(TaskId::from_url(&url).is_ok());
+
+        let url = Url::parse("http://localhost:8080/api/requests/search").unwrap();
+        assert!(TaskRequestId::from_url(&url).is_err());
+    }
+
+    #[test]
+    fn resource_id() {
+        let mut site = crate::fixtures::site();
+        assert!(matches!(site.id(), Err(Error::MissingLink(_))));
+
+        site.links.insert(
+            "self".into(),
+            Url::parse("http://localhost:8080/api/sites/7").unwrap(),
+        );
+        assert_eq!(site.id().unwrap().get(), 7);
+        assert_eq!(
+            site.uri().unwrap(),
+            "http://localhost:8080/api/sites/7".parse().unwrap()
+        );
+        assert_eq!(
+            site.resource_link::<Site>(&LinkRel::SelfLink).unwrap().id(),
+            SiteId::new(7)
+        );
+        assert!(site.resource_link::<Task>(&LinkRel::SelfLink).is_err());
+    }
+
+    #[test]
+    fn resource_uri() {
+        let uri: ResourceUri<TaskRequest> = "https://test-api.atlasground.com/api/requests/190029"
+            .parse()
+            .unwrap();
+        assert_eq!(uri.id(), TaskRequestId::new(190029));
+
+        assert!(
+            "https://test-api.atlasground.com/api/sites/1"
+                .parse::<ResourceUri<TaskRequest>>()
+                .is_err()
+        );
+        assert!("not a url".parse::<ResourceUri<Site>>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn resource_uri_serde() {
+        let uri: ResourceUri<SiteConfiguration> =
+            serde_json::from_str(r#""https://test-api.atlasground.com/api/configurations/1""#)
+                .unwrap();
+        assert_eq!(uri.id().get(), 1);
+        assert_eq!(
+            serde_json::to_string(&uri).unwrap(),
+            r#""https://test-api.atlasground.com/api/configurations/1""#
+        );
+
+        assert!(
+            serde_json::from_str::<ResourceUri<Site>>(
+                r#""https://test-api.atlasground.com/api/configurations/1""#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn ids_as_keys() {
+        let mut by_id = HashMap::new();
+        by_id.insert(SiteId::new(1), "first");
+        by_id.insert(SiteId::new(2), "second");
+
+        assert_eq!(by_id[&SiteId::new(2)], "second");
+        assert_eq!(format!("{:?}", SiteId::new(1)), "Id<Site>(1)");
+        assert_eq!(SiteId::new(1).to_string(), "1");
+    }
+}
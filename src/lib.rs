@@ -3,12 +3,28 @@
 pub mod account;
 pub mod azel;
 pub mod band;
+pub mod cidr;
+pub mod diff;
+pub mod environment;
 pub mod error;
+#[cfg(test)]
+mod fixtures;
 pub mod gateway_licenses;
+pub mod id;
+pub mod links;
 #[cfg(feature = "serde")]
 pub mod pagination;
+pub mod passes;
+pub mod pricing;
+pub mod properties;
+pub mod query;
+pub mod realtime;
+#[cfg(feature = "redact")]
+pub mod redact;
+pub mod retention;
 pub mod satellite;
 pub mod satellite_configuration;
+pub mod secret;
 pub mod site;
 pub mod status;
 pub mod task;
@@ -22,4 +38,63 @@ pub trait Hateoas {
     fn get_links(&self) -> &std::collections::HashMap<String, url::Url>;
 
     fn get_links_mut(&mut self) -> &mut std::collections::HashMap<String, url::Url>;
+
+    /// The relations this model is expected to carry
+    fn expected_links(&self) -> &'static [links::LinkRel] {
+        &[]
+    }
+
+    fn link(&self, rel: &links::LinkRel) -> Option<&url::Url> {
+        self.get_links().get(rel.as_str())
+    }
+
+    /// The link for the relation, or an error naming the relation when it is missing
+    fn require_link(&self, rel: &links::LinkRel) -> Result<&url::Url, error::Error> {
+        self.link(rel)
+            .ok_or_else(|| error::Error::MissingLink(rel.to_string()))
+    }
+
+    /// The link of the relation, as the typed URI of the resource it points to
+    fn resource_link<T: id::Resource>(
+        &self,
+        rel: &links::LinkRel,
+    ) -> Result<id::ResourceUri<T>, error::Error>
+    where
+        Self: Sized,
+    {
+        id::ResourceUri::new(self.require_link(rel)?.clone())
+    }
+
+    /// The full view of the links, keeping every link of a relation and their attributes
+    ///
+    /// By default this is built from [`Hateoas::get_links`], so only the href of the first link
+    /// of each relation is available.
+    fn full_links(&self) -> std::borrow::Cow<'_, links::LinkMap> {
+        std::borrow::Cow::Owned(links::LinkMap::from(self.get_links()))
+    }
+
+    /// The full view of the links, for models which keep one besides the simple view
+    fn full_links_mut(&mut self) -> Option<&mut links::LinkMap> {
+        None
+    }
+
+    fn self_link(&self) -> Option<&url::Url> {
+        self.link(&links::LinkRel::SelfLink)
+    }
+
+    /// The expected relations which are not present
+    fn missing_links(&self) -> Vec<&'static links::LinkRel> {
+        self.expected_links()
+            .iter()
+            .filter(|rel| self.link(rel).is_none())
+            .collect()
+    }
+
+    /// Checks that every expected relation is present, returning an error for the first missing
+    fn validate_links(&self) -> Result<(), error::Error> {
+        match self.missing_links().first() {
+            Some(rel) => Err(error::Error::MissingLink(rel.to_string())),
+            None => Ok(()),
+        }
+    }
 }
@@ -0,0 +1,756 @@
+//! # Links
+//!
+//! Typed relations for navigating the HATEOAS links of Freedom models, along with the full HAL
+//! representation of a link.
+
+use std::collections::HashMap;
+
+use strum::{AsRefStr, EnumString};
+use url::Url;
+
+use crate::error::Error;
+
+/// The relation of a link to the model holding it, the key of the link in `_links`
+///
+/// Relations not known to this crate are kept as [`LinkRel::Custom`], so parsing a relation never
+/// fails. Relations are compared, ordered and hashed by their key, so a custom relation with the
+/// key of a known one, such as `LinkRel::Custom("satellite".into())`, equals the known relation.
+#[derive(Debug, Clone, AsRefStr, EnumString)]
+#[strum(serialize_all = "camelCase")]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum LinkRel {
+    /// The canonical URL of the model itself
+    #[strum(serialize = "self")]
+    SelfLink,
+    Account,
+    Band,
+    Bands,
+    Configuration,
+    Overrides,
+    Request,
+    Satellite,
+    Site,
+    Task,
+    User,
+    First,
+    Prev,
+    Next,
+    Last,
+    Profile,
+    Search,
+    #[strum(default)]
+    Custom(String),
+}
+
+impl LinkRel {
+    /// The key of the relation in the `_links` map
+    pub fn as_str(&self) -> &str {
+        match self {
+            LinkRel::Custom(custom) => custom.as_str(),
+            known => known.as_ref(),
+        }
+    }
+}
+
+impl PartialEq for LinkRel {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for LinkRel {}
+
+impl PartialOrd for LinkRel {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LinkRel {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl std::hash::Hash for LinkRel {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl std::fmt::Display for LinkRel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single HAL link, keeping every attribute of the link
+///
+/// When [`Link::templated`] is set, the href is an
+/// [RFC 6570](https://www.rfc-editor.org/rfc/rfc6570) URI template, which must be expanded
+/// before use.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct Link {
+    pub href: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    pub templated: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub title: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub media_type: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub profile: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub deprecation: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl Link {
+    pub fn new(href: impl Into<String>) -> Self {
+        Self {
+            href: href.into(),
+            ..Self::default()
+        }
+    }
+
+    /// The URL of the link, expanding a template without any variables
+    pub fn url(&self) -> Result<Url, Error> {
+        self.expand(std::iter::empty::<(&str, &str)>())
+    }
+
+    /// Expands the template of the link with the provided variables
+    ///
+    /// Variables missing from `vars` are left out of the expansion, as RFC 6570 specifies.
+    /// Links which are not templated are returned as-is.
+    pub fn expand<I, K, V>(&self, vars: I) -> Result<Url, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let href = self.expand_href(vars);
+
+        Url::parse(&href).map_err(|e| Error::Link(format!("{href}: {e}")))
+    }
+
+    /// Whether the href is relative, so it must be resolved against an
+    /// [`Environment`](crate::environment::Environment) before use
+    pub fn is_relative(&self) -> bool {
+        let href = self.expand_href(std::iter::empty::<(&str, &str)>());
+
+        Url::parse(&href) == Err(url::ParseError::RelativeUrlWithoutBase)
+    }
+
+    /// Expands the template of the href, without parsing the result as a URL
+    pub(crate) fn expand_href<I, K, V>(&self, vars: I) -> String
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        match self.templated {
+            true => {
+                let vars: HashMap<String, String> = vars
+                    .into_iter()
+                    .map(|(k, v)| (k.as_ref().to_owned(), v.as_ref().to_owned()))
+                    .collect();
+                expand_template(&self.href, &vars)
+            }
+            false => self.href.clone(),
+        }
+    }
+}
+
+impl From<Url> for Link {
+    fn from(url: Url) -> Self {
+        Self::new(url)
+    }
+}
+
+/// Every link of a model by relation, including relations holding several links
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkMap(
+    #[cfg_attr(feature = "serde", serde(with = "one_or_many"))] pub HashMap<String, Vec<Link>>,
+);
+
+impl LinkMap {
+    /// The first link of the relation
+    pub fn get(&self, rel: &LinkRel) -> Option<&Link> {
+        self.get_all(rel).first()
+    }
+
+    /// Every link of the relation
+    pub fn get_all(&self, rel: &LinkRel) -> &[Link] {
+        self.0
+            .get(rel.as_str())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The simple view of the links, the URL of the first link of each relation
+    ///
+    /// Templates are expanded without any variables. Relative links are left out, as they can
+    /// only be resolved against an [`Environment`](crate::environment::Environment).
+    pub fn to_urls(&self) -> Result<HashMap<String, Url>, Error> {
+        self.0
+            .iter()
+            .filter_map(|(rel, links)| links.first().map(|link| (rel, link)))
+            .filter(|(_, link)| !link.is_relative())
+            .map(|(rel, link)| Ok((rel.clone(), link.url()?)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl LinkMap {
+    /// The full links, brought up to date with the simple view of the links
+    ///
+    /// The simple view is the one edited through [`Hateoas::get_links_mut`](crate::Hateoas), so
+    /// the first link of a relation takes the URL of the simple view when they differ, keeping
+    /// its other attributes, and relations missing from the simple view are dropped. Relative
+    /// links can't be held by the simple view, so they are kept.
+    fn merge_urls(&self, urls: &HashMap<String, Url>) -> LinkMap {
+        let mut merged: HashMap<String, Vec<Link>> = self
+            .0
+            .iter()
+            .filter(|(rel, links)| {
+                urls.contains_key(rel.as_str()) || links.first().is_some_and(Link::is_relative)
+            })
+            .map(|(rel, links)| (rel.clone(), links.clone()))
+            .collect();
+        for (rel, url) in urls {
+            let links = merged.entry(rel.clone()).or_default();
+            match links.first_mut() {
+                Some(first) if first.url().as_ref() == Ok(url) => {}
+                Some(first) => {
+                    first.href = url.to_string();
+                    first.templated = false;
+                }
+                None => links.push(Link::from(url.clone())),
+            }
+        }
+
+        LinkMap(merged)
+    }
+}
+
+impl From<&HashMap<String, Url>> for LinkMap {
+    fn from(links: &HashMap<String, Url>) -> Self {
+        Self(
+            links
+                .iter()
+                .map(|(rel, url)| (rel.clone(), vec![Link::from(url.clone())]))
+                .collect(),
+        )
+    }
+}
+
+/// A model paired with the full view of its links
+///
+/// The models only keep the simple view of their links, this wrapper deserializes the `_links`
+/// of the payload a second time, keeping every link of a relation along with its attributes.
+///
+/// The simple view of the inner model stays the one which is edited, such as by
+/// [`Environment::rebase_links`](crate::environment::Environment::rebase_links), and the full
+/// links are brought up to date with it when serializing.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct Linked<T> {
+    pub inner: T,
+    pub links: LinkMap,
+}
+
+#[cfg(feature = "serde")]
+impl<T> Linked<T> {
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> std::ops::Deref for Linked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: crate::Hateoas> crate::Hateoas for Linked<T> {
+    fn get_links(&self) -> &HashMap<String, Url> {
+        self.inner.get_links()
+    }
+
+    fn get_links_mut(&mut self) -> &mut HashMap<String, Url> {
+        self.inner.get_links_mut()
+    }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        self.inner.expected_links()
+    }
+
+    fn full_links(&self) -> std::borrow::Cow<'_, LinkMap> {
+        std::borrow::Cow::Borrowed(&self.links)
+    }
+
+    fn full_links_mut(&mut self) -> Option<&mut LinkMap> {
+        Some(&mut self.links)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Linked<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as _;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let links = match value.get("_links") {
+            Some(links) => serde_json::from_value(links.clone()).map_err(D::Error::custom)?,
+            None => LinkMap::default(),
+        };
+        let inner = serde_json::from_value(value).map_err(D::Error::custom)?;
+
+        Ok(Self { inner, links })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Linked<T>
+where
+    T: serde::Serialize + crate::Hateoas,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error as _;
+
+        let mut value = serde_json::to_value(&self.inner).map_err(S::Error::custom)?;
+        if let Some(object) = value.as_object_mut() {
+            let links = self.links.merge_urls(self.inner.get_links());
+            let links = serde_json::to_value(&links).map_err(S::Error::custom)?;
+            object.insert("_links".to_owned(), links);
+        }
+        value.serialize(serializer)
+    }
+}
+
+/// HAL allows a relation to hold either a single link object, or an array of them
+#[cfg(feature = "serde")]
+pub(crate) mod one_or_many {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Link;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+
+    pub(crate) fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<HashMap<String, Vec<Link>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let map: HashMap<String, OneOrMany<Link>> = HashMap::deserialize(deserializer)?;
+        Ok(map
+            .into_iter()
+            .map(|(rel, links)| match links {
+                OneOrMany::One(link) => (rel, vec![link]),
+                OneOrMany::Many(links) => (rel, links),
+            })
+            .collect())
+    }
+
+    pub(crate) fn serialize<S>(
+        val: &HashMap<String, Vec<Link>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(val.len()))?;
+        for (rel, links) in val {
+            match links.as_slice() {
+                [link] => map.serialize_entry(rel, link)?,
+                links => map.serialize_entry(rel, links)?,
+            }
+        }
+        map.end()
+    }
+}
+
+/// Expands an RFC 6570 URI template, up to level 3, with string variables
+fn expand_template(template: &str, vars: &HashMap<String, String>) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            // Unterminated expression, keep it verbatim
+            expanded.push_str(&rest[start..]);
+            return expanded;
+        };
+        expanded.push_str(&expand_expression(&rest[start + 1..start + len], vars));
+        rest = &rest[start + len + 1..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+fn expand_expression(expression: &str, vars: &HashMap<String, String>) -> String {
+    // (operator, first, separator, named, if empty, allow reserved)
+    let (operator, first, sep, named, if_empty, reserved) = match expression.chars().next() {
+        Some('+') => ("+", "", ",", false, "", true),
+        Some('#') => ("#", "#", ",", false, "", true),
+        Some('.') => (".", ".", ".", false, "", false),
+        Some('/') => ("/", "/", "/", false, "", false),
+        Some(';') => (";", ";", ";", true, "", false),
+        Some('?') => ("?", "?", "&", true, "=", false),
+        Some('&') => ("&", "&", "&", true, "=", false),
+        _ => ("", "", ",", false, "", false),
+    };
+
+    let expanded: Vec<String> = expression[operator.len()..]
+        .split(',')
+        .filter_map(|spec| {
+            let spec = spec.trim_end_matches('*');
+            let (name, max_len) = match spec.split_once(':') {
+                Some((name, max_len)) => (name, max_len.parse::<usize>().ok()),
+                None => (spec, None),
+            };
+            let value = vars.get(name)?;
+            let value: String = match max_len {
+                Some(max_len) => value.chars().take(max_len).collect(),
+                None => value.clone(),
+            };
+            let value = encode(&value, reserved);
+
+            Some(match (named, value.is_empty()) {
+                (true, true) => format!("{name}{if_empty}"),
+                (true, false) => format!("{name}={value}"),
+                (false, _) => value,
+            })
+        })
+        .collect();
+
+    match expanded.is_empty() {
+        true => String::new(),
+        false => format!("{first}{}", expanded.join(sep)),
+    }
+}
+
+fn encode(value: &str, allow_reserved: bool) -> String {
+    const RESERVED: &str = ":/?#[]@!$&'()*+,;=";
+
+    let mut encoded = String::with_capacity(value.len());
+    let bytes = value.as_bytes();
+    for (idx, &byte) in bytes.iter().enumerate() {
+        let c = byte as char;
+        let unreserved = byte.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~');
+        let kept_reserved = allow_reserved
+            && (RESERVED.contains(c)
+                || (c == '%'
+                    && bytes
+                        .get(idx + 1..idx + 3)
+                        .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit))));
+
+        if byte.is_ascii() && (unreserved || kept_reserved) {
+            encoded.push(c);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Hateoas;
+
+    use super::*;
+
+    struct Model {
+        links: HashMap<String, Url>,
+    }
+
+    impl Hateoas for Model {
+        fn get_links(&self) -> &HashMap<String, Url> {
+            &self.links
+        }
+
+        fn get_links_mut(&mut self) -> &mut HashMap<String, Url> {
+            &mut self.links
+        }
+
+        fn expected_links(&self) -> &'static [LinkRel] {
+            &[LinkRel::SelfLink, LinkRel::Satellite]
+        }
+    }
+
+    #[test]
+    fn parse_relations() {
+        assert_eq!("self".parse(), Ok(LinkRel::SelfLink));
+        assert_eq!("satellite".parse(), Ok(LinkRel::Satellite));
+        assert_eq!(
+            "sharedWith".parse(),
+            Ok(LinkRel::Custom("sharedWith".into()))
+        );
+        assert_eq!(LinkRel::SelfLink.as_str(), "self");
+        assert_eq!(LinkRel::Custom("sharedWith".into()).as_str(), "sharedWith");
+    }
+
+    #[test]
+    fn custom_relations_equal_known_ones() {
+        let custom = LinkRel::Custom("satellite".into());
+        assert_eq!(custom, LinkRel::Satellite);
+        assert_ne!(LinkRel::Custom("Satellite".into()), LinkRel::Satellite);
+
+        let relations = std::collections::HashSet::from([LinkRel::Satellite, custom]);
+        assert_eq!(relations.len(), 1);
+
+        let model = Model {
+            links: HashMap::from([("self".into(), Url::parse("http://localhost/").unwrap())]),
+        };
+        assert!(model.link(&LinkRel::Custom("self".into())).is_some());
+    }
+
+    #[test]
+    fn expand_templates() {
+        let vars = HashMap::from([
+            ("projection".to_owned(), "summary".to_owned()),
+            ("page".to_owned(), "2".to_owned()),
+            ("path".to_owned(), "a/b c".to_owned()),
+        ]);
+
+        assert_eq!(
+            expand_template("/requests{?projection,page,size}", &vars),
+            "/requests?projection=summary&page=2"
+        );
+        assert_eq!(expand_template("/requests{?size}", &vars), "/requests");
+        assert_eq!(expand_template("/x/{path}", &vars), "/x/a%2Fb%20c");
+        assert_eq!(expand_template("/x/{+path}", &vars), "/x/a/b%20c");
+        assert_eq!(expand_template("/x{/page}{&size}", &vars), "/x/2");
+        assert_eq!(
+            expand_template("/x{;page}{#projection:3}", &vars),
+            "/x;page=2#sum"
+        );
+    }
+
+    #[test]
+    fn templated_link() {
+        let link = Link {
+            templated: true,
+            ..Link::new("http://localhost:8080/api/requests{?projection}")
+        };
+
+        assert_eq!(
+            link.url().unwrap().as_str(),
+            "http://localhost:8080/api/requests"
+        );
+        assert_eq!(
+            link.expand([("projection", "full")]).unwrap().as_str(),
+            "http://localhost:8080/api/requests?projection=full"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn link_map_deserialize() {
+        let json = serde_json::json!({
+            "self": {
+                "href": "http://localhost:8080/api/requests{?projection}",
+                "templated": true
+            },
+            "item": [
+                { "href": "http://localhost:8080/api/requests/1", "title": "First" },
+                { "href": "http://localhost:8080/api/requests/2", "name": "second" }
+            ]
+        });
+
+        let links: LinkMap = serde_json::from_value(json.clone()).unwrap();
+        let items = links.get_all(&LinkRel::Custom("item".into()));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title.as_deref(), Some("First"));
+        assert_eq!(items[1].name.as_deref(), Some("second"));
+        assert!(links.get(&LinkRel::SelfLink).unwrap().templated);
+
+        let urls = links.to_urls().unwrap();
+        assert_eq!(
+            urls["item"].as_str(),
+            "http://localhost:8080/api/requests/1"
+        );
+        assert_eq!(serde_json::to_value(&links).unwrap(), json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn linked_keeps_full_links() {
+        use crate::user::User;
+
+        let json = serde_json::json!({
+            "created": "2024-01-01T00:00:00.000Z",
+            "firstName": "Jane",
+            "lastName": "Doe",
+            "verified": true,
+            "email": "jane@example.com",
+            "preferences": {
+                "visibilityDays": 7,
+                "minElevation": 10.0,
+                "maxElevation": 90.0,
+                "minDuration": 5.0,
+                "elevationTolerance": 0.0,
+                "durationTolerance": 0.0,
+                "notifyViaEmail": false,
+                "notifyViaText": false
+            },
+            "apiAccessEnabled": true,
+            "_links": {
+                "self": { "href": "http://localhost:8080/api/users/1" },
+                "account": [
+                    { "href": "http://localhost:8080/api/accounts/1", "title": "Primary" },
+                    { "href": "http://localhost:8080/api/accounts/2" }
+                ],
+                "search": {
+                    "href": "http://localhost:8080/api/users/search{?email}",
+                    "templated": true
+                }
+            }
+        });
+
+        let user: Linked<User> = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            user.account_link().unwrap().as_str(),
+            "http://localhost:8080/api/accounts/1"
+        );
+        assert_eq!(
+            user.link(&LinkRel::Search).unwrap().as_str(),
+            "http://localhost:8080/api/users/search"
+        );
+
+        let full = user.full_links();
+        assert_eq!(full.get_all(&LinkRel::Account).len(), 2);
+        assert_eq!(
+            full.get(&LinkRel::Account).unwrap().title.as_deref(),
+            Some("Primary")
+        );
+        let search = full.get(&LinkRel::Search).unwrap();
+        assert_eq!(
+            search
+                .expand([("email", "jane@example.com")])
+                .unwrap()
+                .as_str(),
+            "http://localhost:8080/api/users/search?email=jane%40example.com"
+        );
+
+        let value = serde_json::to_value(&user).unwrap();
+        assert!(value["_links"]["account"].is_array());
+    }
+
+    #[test]
+    fn missing_expected_links() {
+        let model = Model {
+            links: HashMap::from([(
+                "self".to_owned(),
+                Url::parse("http://localhost:8080/api/overrides/233").unwrap(),
+            )]),
+        };
+
+        assert!(model.self_link().is_some());
+        assert_eq!(model.missing_links(), vec![&LinkRel::Satellite]);
+        assert_eq!(
+            model.require_link(&LinkRel::Satellite),
+            Err(Error::MissingLink("satellite".into()))
+        );
+        assert_eq!(
+            model.validate_links(),
+            Err(Error::MissingLink("satellite".into()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn linked_serializes_edited_links() {
+        use crate::environment::Environment;
+
+        let test_api = |path: &str| format!("https://test-api.atlasground.com/api/{path}");
+        let links = HashMap::from([
+            ("self".to_owned(), Url::parse(&test_api("users/1")).unwrap()),
+            (
+                "profile".to_owned(),
+                Url::parse(&test_api("profile")).unwrap(),
+            ),
+        ]);
+        let mut user = Linked {
+            inner: crate::user::User {
+                links: links.clone(),
+                ..crate::fixtures::user()
+            },
+            links: LinkMap::from(&links),
+        };
+        let own = user.links.0.get_mut("self").unwrap();
+        own[0].title = Some("Jane".into());
+        own.push(Link::new(test_api("users/1{?projection}")));
+        user.get_links_mut().remove("profile");
+
+        let gateway = Environment::custom(Url::parse("https://gateway.internal/api").unwrap());
+        assert_eq!(Environment::Test.rebase_links(&mut user, &gateway), 1);
+
+        let value = serde_json::to_value(&user).unwrap();
+        assert_eq!(
+            value["_links"],
+            serde_json::json!({
+                "self": [
+                    { "href": "https://gateway.internal/api/users/1", "title": "Jane" },
+                    { "href": "https://gateway.internal/api/users/1{?projection}" }
+                ]
+            })
+        );
+    }
+}
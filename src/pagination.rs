@@ -1,29 +1,214 @@
 use std::collections::HashMap;
 
 use crate::Hateoas;
+use crate::error::Error;
+use crate::links::LinkRel;
 
 use super::utils;
-use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned};
 use url::Url;
 
 /// A paginated response
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
+///
+/// When serialized, the items are embedded under [`Paginated::relation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
 pub struct Paginated<T>
 where
     T: DeserializeOwned,
 {
-    #[serde(rename = "_embedded")]
-    #[serde(deserialize_with = "utils::destructure::serde::deserialize")]
     pub items: Vec<T>,
+    /// The name of the relation the items are embedded under, such as `satellites`
+    pub relation: String,
+    pub links: HashMap<String, Url>,
+    pub page: Page,
+}
+
+impl<T> Paginated<T>
+where
+    T: DeserializeOwned,
+{
+    pub fn new(relation: impl Into<String>, items: Vec<T>, page: Page) -> Self {
+        Self {
+            items,
+            relation: relation.into(),
+            links: HashMap::new(),
+            page,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PaginatedRepr<T>
+where
+    T: DeserializeOwned,
+{
+    #[serde(rename = "_embedded")]
+    #[serde(deserialize_with = "utils::destructure::serde::deserialize_named")]
+    embedded: (String, Vec<T>),
     #[serde(rename = "_links")]
     #[serde(with = "utils::links::serde")]
+    links: HashMap<String, Url>,
+    page: Page,
+}
+
+impl<'de, T> Deserialize<'de> for Paginated<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let PaginatedRepr {
+            embedded: (relation, items),
+            links,
+            page,
+        } = PaginatedRepr::deserialize(deserializer)?;
+
+        Ok(Self {
+            items,
+            relation,
+            links,
+            page,
+        })
+    }
+}
+
+impl<T> Serialize for Paginated<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Paginated", 3)?;
+        state.serialize_field(
+            "_embedded",
+            &utils::destructure::serde::Named(&self.relation, &self.items),
+        )?;
+        state.serialize_field("_links", &utils::links::serde::Links(&self.links))?;
+        state.serialize_field("page", &self.page)?;
+        state.end()
+    }
+}
+
+impl<T> Hateoas for Paginated<T>
+where
+    T: DeserializeOwned,
+{
+    fn get_links(&self) -> &HashMap<String, url::Url> {
+        &self.links
+    }
+
+    fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
+        &mut self.links
+    }
+}
+
+/// A paginated response which keeps the valid items when some items fail to deserialize
+///
+/// Each failed item is recorded in [`LenientPaginated::errors`], rather than failing the whole
+/// page.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct LenientPaginated<T>
+where
+    T: DeserializeOwned,
+{
+    pub items: Vec<T>,
+    pub errors: Vec<ItemError>,
+    /// The name of the relation the items are embedded under, such as `satellites`
+    pub relation: String,
     pub links: HashMap<String, Url>,
     pub page: Page,
 }
 
-impl<T> Hateoas for Paginated<T>
+/// An item of a page which failed to deserialize
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct ItemError {
+    /// The index of the item within the page
+    pub index: usize,
+    /// The item as it was received
+    pub raw: String,
+    pub error: String,
+}
+
+impl std::fmt::Display for ItemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Item {} failed to deserialize: {}",
+            self.index, self.error
+        )
+    }
+}
+
+impl<T> LenientPaginated<T>
+where
+    T: DeserializeOwned,
+{
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Converts to a [`Paginated`], failing with [`Error::PaginatedInner`] if any item failed
+    pub fn into_strict(self) -> Result<Paginated<T>, Error> {
+        if !self.is_complete() {
+            return Err(Error::PaginatedInner);
+        }
+
+        Ok(Paginated {
+            items: self.items,
+            relation: self.relation,
+            links: self.links,
+            page: self.page,
+        })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LenientPaginated<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let PaginatedRepr {
+            embedded: (relation, values),
+            links,
+            page,
+        } = PaginatedRepr::<serde_json::Value>::deserialize(deserializer)?;
+
+        let mut items = Vec::with_capacity(values.len());
+        let mut errors = Vec::new();
+        for (index, value) in values.into_iter().enumerate() {
+            match T::deserialize(&value) {
+                Ok(item) => items.push(item),
+                Err(error) => errors.push(ItemError {
+                    index,
+                    raw: value.to_string(),
+                    error: error.to_string(),
+                }),
+            }
+        }
+
+        Ok(Self {
+            items,
+            errors,
+            relation,
+            links,
+            page,
+        })
+    }
+}
+
+impl<T> Hateoas for LenientPaginated<T>
 where
     T: DeserializeOwned,
 {
@@ -36,6 +221,149 @@ where
     }
 }
 
+impl<T> Paginated<T>
+where
+    T: DeserializeOwned,
+{
+    pub fn next_url(&self) -> Option<&Url> {
+        self.link(&LinkRel::Next)
+    }
+
+    pub fn prev_url(&self) -> Option<&Url> {
+        self.link(&LinkRel::Prev)
+    }
+
+    pub fn first_url(&self) -> Option<&Url> {
+        self.link(&LinkRel::First)
+    }
+
+    pub fn last_url(&self) -> Option<&Url> {
+        self.link(&LinkRel::Last)
+    }
+
+    /// Whether there is a page after this one, according to the links or the page metadata
+    pub fn has_next(&self) -> bool {
+        self.next_url().is_some() || self.page.number.saturating_add(1) < self.page.total_pages
+    }
+
+    pub fn is_last(&self) -> bool {
+        !self.has_next()
+    }
+
+    /// The URL of the zero-indexed page `number`, computed from the self link
+    ///
+    /// Useful when the API omits the navigation links. Returns `None` when the page is out of
+    /// range, or there is no self link to compute from.
+    pub fn page_url(&self, number: u32) -> Option<Url> {
+        if number >= self.page.total_pages {
+            return None;
+        }
+
+        self.self_link()
+            .map(|base| page_url(base, number, self.page.size))
+    }
+
+    /// The URL of the next page, from the links when present, and computed otherwise
+    pub fn next_page_url(&self) -> Option<Url> {
+        match self.next_url() {
+            Some(next) => Some(next.clone()),
+            None => self.page_url(self.page.number.checked_add(1)?),
+        }
+    }
+}
+
+/// Sets the `page` and `size` query parameters of the URL, keeping every other parameter
+pub fn page_url(base: &Url, number: u32, size: u32) -> Url {
+    let mut url = base.clone();
+    let kept: Vec<(String, String)> = base
+        .query_pairs()
+        .filter(|(key, _)| key != "page" && key != "size")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(kept)
+        .append_pair("page", &number.to_string())
+        .append_pair("size", &size.to_string());
+    url
+}
+
+/// Fetches a single page of a paginated resource, for any blocking HTTP client
+pub trait PageFetcher<T>
+where
+    T: DeserializeOwned,
+{
+    type Error;
+
+    fn fetch_page(&mut self, url: &Url) -> Result<Paginated<T>, Self::Error>;
+
+    /// Fetches every page starting from `first`, collecting all of the items
+    fn fetch_all(&mut self, first: &Url) -> Result<Vec<T>, Self::Error> {
+        let mut items = Vec::new();
+        let mut url = Some(first.clone());
+        let mut fetched = 0u32;
+
+        while let Some(current) = url {
+            let page = self.fetch_page(&current)?;
+            fetched += 1;
+            url = next_to_fetch(&page, &current, fetched);
+            items.extend(page.items);
+        }
+
+        Ok(items)
+    }
+}
+
+/// Fetches a single page of a paginated resource, for any async HTTP client
+///
+/// The returned futures are `Send`, so they can be spawned onto multi-threaded runtimes.
+pub trait AsyncPageFetcher<T>: Send
+where
+    T: DeserializeOwned + Send,
+{
+    type Error;
+
+    fn fetch_page(
+        &mut self,
+        url: &Url,
+    ) -> impl std::future::Future<Output = Result<Paginated<T>, Self::Error>> + Send;
+
+    /// Fetches every page starting from `first`, collecting all of the items
+    fn fetch_all(
+        &mut self,
+        first: &Url,
+    ) -> impl std::future::Future<Output = Result<Vec<T>, Self::Error>> + Send {
+        async move {
+            let mut items = Vec::new();
+            let mut url = Some(first.clone());
+            let mut fetched = 0u32;
+
+            while let Some(current) = url {
+                let page = self.fetch_page(&current).await?;
+                fetched += 1;
+                url = next_to_fetch(&page, &current, fetched);
+                items.extend(page.items);
+            }
+
+            Ok(items)
+        }
+    }
+}
+
+/// The next URL to fetch, guarding against links which point back at the current page and
+/// against fetching more pages than the API reports
+fn next_to_fetch<T>(page: &Paginated<T>, current: &Url, fetched: u32) -> Option<Url>
+where
+    T: DeserializeOwned,
+{
+    if fetched >= page.page.total_pages {
+        return None;
+    }
+
+    page.next_page_url().filter(|next| next != current)
+}
+
 /// Page metadata included in a paginated stream
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -46,3 +374,174 @@ pub struct Page {
     pub total_pages: u32,
     pub number: u32,
 }
+
+impl Page {
+    pub fn new(size: u32, total_elements: u32, total_pages: u32, number: u32) -> Self {
+        Self {
+            size,
+            total_elements,
+            total_pages,
+            number,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(number: u32, total_pages: u32, next: bool) -> Paginated<u32> {
+        let mut links = serde_json::json!({
+            "self": { "href": format!("http://localhost:8080/api/satellites?sort=name&page={number}&size=2") },
+        });
+        if next {
+            links["next"] = serde_json::json!({
+                "href": format!("http://localhost:8080/api/satellites?sort=name&page={}&size=2", number + 1)
+            });
+        }
+        let json = serde_json::json!({
+            "_embedded": { "satellites": [number * 2, number * 2 + 1] },
+            "_links": links,
+            "page": { "size": 2, "totalElements": total_pages * 2, "totalPages": total_pages, "number": number },
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let mut paginated = Paginated::new("satellites", vec![1u32, 2], Page::new(2, 2, 1, 0));
+        paginated.links.insert(
+            "self".into(),
+            Url::parse("http://localhost:8080/api/satellites").unwrap(),
+        );
+
+        let value = serde_json::to_value(&paginated).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "_embedded": { "satellites": [1, 2] },
+                "_links": { "self": { "href": "http://localhost:8080/api/satellites" } },
+                "page": { "size": 2, "totalElements": 2, "totalPages": 1, "number": 0 },
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Paginated<u32>>(value).unwrap(),
+            paginated
+        );
+    }
+
+    #[test]
+    fn lenient_keeps_valid_items() {
+        let json = serde_json::json!({
+            "_embedded": { "satellites": [1, "two", 3, -4] },
+            "_links": { "self": { "href": "http://localhost:8080/api/satellites" } },
+            "page": { "size": 4, "totalElements": 4, "totalPages": 1, "number": 0 },
+        });
+
+        assert!(serde_json::from_value::<Paginated<u32>>(json.clone()).is_err());
+
+        let lenient: LenientPaginated<u32> = serde_json::from_value(json).unwrap();
+        assert_eq!(lenient.items, [1, 3]);
+        let failed: Vec<_> = lenient
+            .errors
+            .iter()
+            .map(|error| (error.index, error.raw.as_str()))
+            .collect();
+        assert_eq!(failed, [(1, r#""two""#), (3, "-4")]);
+        assert_eq!(lenient.into_strict(), Err(Error::PaginatedInner));
+    }
+
+    #[test]
+    fn navigation() {
+        let first = page(0, 3, true);
+        assert!(first.has_next());
+        assert_eq!(
+            first.next_url().unwrap().as_str(),
+            "http://localhost:8080/api/satellites?sort=name&page=1&size=2"
+        );
+        assert!(first.prev_url().is_none());
+
+        // Missing links are computed from the page metadata
+        let middle = page(1, 3, false);
+        assert!(middle.has_next());
+        assert_eq!(
+            middle.next_page_url().unwrap().as_str(),
+            "http://localhost:8080/api/satellites?sort=name&page=2&size=2"
+        );
+        assert!(middle.page_url(3).is_none());
+
+        assert!(page(2, 3, false).is_last());
+    }
+
+    struct Fetcher {
+        requested: Vec<Url>,
+    }
+
+    impl Fetcher {
+        fn respond(&mut self, url: &Url) -> Paginated<u32> {
+            self.requested.push(url.clone());
+            let number = url
+                .query_pairs()
+                .find(|(key, _)| key == "page")
+                .map(|(_, value)| value.parse().unwrap())
+                .unwrap_or(0);
+            page(number, 3, number == 0)
+        }
+    }
+
+    impl PageFetcher<u32> for Fetcher {
+        type Error = std::convert::Infallible;
+
+        fn fetch_page(&mut self, url: &Url) -> Result<Paginated<u32>, Self::Error> {
+            Ok(self.respond(url))
+        }
+    }
+
+    impl AsyncPageFetcher<u32> for Fetcher {
+        type Error = std::convert::Infallible;
+
+        async fn fetch_page(&mut self, url: &Url) -> Result<Paginated<u32>, Self::Error> {
+            Ok(self.respond(url))
+        }
+    }
+
+    #[test]
+    fn fetch_all_pages() {
+        let first = Url::parse("http://localhost:8080/api/satellites?sort=name").unwrap();
+        let mut fetcher = Fetcher {
+            requested: Vec::new(),
+        };
+
+        let items = PageFetcher::fetch_all(&mut fetcher, &first).unwrap();
+        assert_eq!(items, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(fetcher.requested.len(), 3);
+    }
+
+    #[test]
+    fn fetch_all_pages_async() {
+        use std::future::Future;
+        use std::task::{Context, Poll, Waker};
+
+        let first = Url::parse("http://localhost:8080/api/satellites?sort=name").unwrap();
+        let mut fetcher = Fetcher {
+            requested: Vec::new(),
+        };
+
+        fn assert_send<F: Send>(future: F) -> F {
+            future
+        }
+
+        // The fetcher never awaits anything, so a single poll drives it to completion
+        let mut future = std::pin::pin!(assert_send(AsyncPageFetcher::fetch_all(
+            &mut fetcher,
+            &first
+        )));
+        let Poll::Ready(items) = future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        else {
+            panic!("fetching did not complete");
+        };
+        assert_eq!(items.unwrap(), [0, 1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,411 @@
+//! # Passes
+//!
+//! Filters and ranks candidate passes by a user's [`UserPreferences`], mirroring the choices
+//! of the Freedom scheduler.
+//!
+//! [`UserPreferences::min_duration`] is in minutes, and elevations are in degrees. The elevation
+//! and duration tolerances loosen the corresponding limits: a pass which only meets a limit
+//! thanks to a tolerance is accepted, but ranked after passes meeting every limit outright.
+
+use time::{Duration, OffsetDateTime};
+
+use crate::{azel::AzEl, user::UserPreferences};
+
+/// A candidate pass over a site
+pub trait Pass {
+    fn start(&self) -> OffsetDateTime;
+
+    fn end(&self) -> OffsetDateTime;
+
+    /// The highest elevation reached during the pass, in degrees
+    fn max_elevation(&self) -> Option<f64>;
+
+    fn duration(&self) -> Duration {
+        self.end() - self.start()
+    }
+}
+
+impl Pass for AzEl {
+    fn start(&self) -> OffsetDateTime {
+        self.start
+    }
+
+    fn end(&self) -> OffsetDateTime {
+        self.end
+    }
+
+    fn max_elevation(&self) -> Option<f64> {
+        self.directions
+            .iter()
+            .map(|direction| direction.el)
+            .max_by(f64::total_cmp)
+    }
+}
+
+/// Why a pass was rejected by the preferences
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum Rejection {
+    /// The pass has already started, so it can no longer be scheduled
+    AlreadyStarted {
+        now: OffsetDateTime,
+    },
+    /// The pass starts after the visibility window
+    BeyondVisibility {
+        latest_start: OffsetDateTime,
+    },
+    /// The pass has no elevation data
+    MissingElevation,
+    ElevationTooLow {
+        max_elevation: f64,
+        limit: f64,
+    },
+    ElevationTooHigh {
+        max_elevation: f64,
+        limit: f64,
+    },
+    TooShort {
+        duration: Duration,
+        limit: Duration,
+    },
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyStarted { now } => write!(f, "Started before {now}"),
+            Rejection::BeyondVisibility { latest_start } => {
+                write!(
+                    f,
+                    "Starts after the visibility window ends at {latest_start}"
+                )
+            }
+            Rejection::MissingElevation => f.write_str("Has no elevation data"),
+            Rejection::ElevationTooLow {
+                max_elevation,
+                limit,
+            } => write!(
+                f,
+                "Peaks at {max_elevation}° elevation, below the minimum of {limit}°"
+            ),
+            Rejection::ElevationTooHigh {
+                max_elevation,
+                limit,
+            } => write!(
+                f,
+                "Peaks at {max_elevation}° elevation, above the maximum of {limit}°"
+            ),
+            Rejection::TooShort { duration, limit } => {
+                write!(f, "Lasts {duration}, shorter than the minimum of {limit}")
+            }
+        }
+    }
+}
+
+/// A pass which satisfies the preferences
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct AcceptedPass<'a, P> {
+    pub pass: &'a P,
+    pub max_elevation: f64,
+    pub duration: Duration,
+    /// Whether the pass relied on a tolerance to be accepted
+    pub within_tolerance: bool,
+}
+
+/// A pass which does not satisfy the preferences, along with every reason why
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct RejectedPass<'a, P> {
+    pub pass: &'a P,
+    pub reasons: Vec<Rejection>,
+}
+
+/// The outcome of applying preferences to a set of passes
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct PassSelection<'a, P> {
+    /// Accepted passes, best first
+    pub accepted: Vec<AcceptedPass<'a, P>>,
+    /// Rejected passes, in the order they were provided
+    pub rejected: Vec<RejectedPass<'a, P>>,
+}
+
+impl UserPreferences {
+    /// Filters the passes by these preferences, then ranks the remaining passes
+    ///
+    /// Passes starting before `now` can't be scheduled anymore, and are always rejected.
+    ///
+    /// Passes meeting every limit outright rank first. Ties are broken by the highest elevation,
+    /// then the longest duration, then the earliest start.
+    pub fn select_passes<'a, P: Pass>(
+        &self,
+        passes: &'a [P],
+        now: OffsetDateTime,
+    ) -> PassSelection<'a, P> {
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+        for pass in passes {
+            match self.evaluate(pass, now) {
+                Ok(evaluation) => accepted.push(evaluation),
+                Err(reasons) => rejected.push(RejectedPass { pass, reasons }),
+            }
+        }
+
+        accepted.sort_by(|a, b| {
+            a.within_tolerance
+                .cmp(&b.within_tolerance)
+                .then(b.max_elevation.total_cmp(&a.max_elevation))
+                .then(b.duration.cmp(&a.duration))
+                .then(a.pass.start().cmp(&b.pass.start()))
+        });
+
+        PassSelection { accepted, rejected }
+    }
+
+    fn evaluate<'a, P: Pass>(
+        &self,
+        pass: &'a P,
+        now: OffsetDateTime,
+    ) -> Result<AcceptedPass<'a, P>, Vec<Rejection>> {
+        let mut reasons = Vec::new();
+        let mut within_tolerance = false;
+
+        if pass.start() < now {
+            reasons.push(Rejection::AlreadyStarted { now });
+        }
+
+        let latest_start = now.saturating_add(Duration::days(self.visibility_days.into()));
+        if pass.start() > latest_start {
+            reasons.push(Rejection::BeyondVisibility { latest_start });
+        }
+
+        let elevation_tolerance = f64::from(self.elevation_tolerance);
+        let min_elevation = f64::from(self.min_elevation);
+        let max_elevation_limit = f64::from(self.max_elevation);
+        let max_elevation = pass.max_elevation();
+        match max_elevation {
+            None => reasons.push(Rejection::MissingElevation),
+            Some(max_elevation) if max_elevation < min_elevation - elevation_tolerance => {
+                reasons.push(Rejection::ElevationTooLow {
+                    max_elevation,
+                    limit: min_elevation,
+                });
+            }
+            Some(max_elevation) if max_elevation > max_elevation_limit + elevation_tolerance => {
+                reasons.push(Rejection::ElevationTooHigh {
+                    max_elevation,
+                    limit: max_elevation_limit,
+                });
+            }
+            Some(max_elevation) => {
+                within_tolerance |=
+                    max_elevation < min_elevation || max_elevation > max_elevation_limit;
+            }
+        }
+
+        let duration = pass.duration();
+        let limit = saturating_minutes(self.min_duration);
+        let tolerance = saturating_minutes(self.duration_tolerance);
+        if duration < limit.saturating_sub(tolerance) {
+            reasons.push(Rejection::TooShort { duration, limit });
+        } else {
+            within_tolerance |= duration < limit;
+        }
+
+        match max_elevation {
+            Some(max_elevation) if reasons.is_empty() => Ok(AcceptedPass {
+                pass,
+                max_elevation,
+                duration,
+                within_tolerance,
+            }),
+            _ => Err(reasons),
+        }
+    }
+}
+
+/// Converts a number of minutes from the preferences, which may be huge or not finite
+///
+/// Values beyond the range of a [`Duration`] saturate, and NaN is treated as no time at all.
+fn saturating_minutes(minutes: f32) -> Duration {
+    let seconds = f64::from(minutes) * 60.0;
+    match Duration::checked_seconds_f64(seconds) {
+        Some(duration) => duration,
+        None if seconds.is_nan() => Duration::ZERO,
+        None if seconds > 0.0 => Duration::MAX,
+        None => Duration::MIN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use crate::azel::{Direction, Location};
+
+    use super::*;
+
+    const NOW: OffsetDateTime = datetime!(2025 - 01 - 01 00:00 UTC);
+
+    fn preferences() -> UserPreferences {
+        UserPreferences {
+            visibility_days: 2,
+            min_elevation: 10.0,
+            max_elevation: 85.0,
+            min_duration: 5.0,
+            elevation_tolerance: 2.0,
+            duration_tolerance: 1.0,
+            notify_via_email: false,
+            notify_via_text: false,
+        }
+    }
+
+    fn pass(start_hours: i64, minutes: i64, peak: f64) -> AzEl {
+        let start = NOW + Duration::hours(start_hours);
+        AzEl {
+            start,
+            end: start + Duration::minutes(minutes),
+            location: Location {
+                longitude: 0.0,
+                latitude: 0.0,
+                elevation: 0.0,
+            },
+            directions: [0.0, peak, 0.0]
+                .into_iter()
+                .map(|el| Direction {
+                    timestamp: start,
+                    az: 0.0,
+                    el,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn ranks_accepted_passes() {
+        let passes = [
+            pass(1, 10, 40.0),
+            pass(2, 10, 60.0),
+            pass(3, 10, 9.0),
+            pass(4, 20, 60.0),
+        ];
+        let selection = preferences().select_passes(&passes, NOW);
+
+        let ranked: Vec<_> = selection
+            .accepted
+            .iter()
+            .map(|accepted| accepted.pass)
+            .collect();
+        assert_eq!(ranked, [&passes[3], &passes[1], &passes[0], &passes[2]]);
+        assert!(selection.accepted[3].within_tolerance);
+        assert!(selection.rejected.is_empty());
+    }
+
+    #[test]
+    fn explains_rejections() {
+        let passes = [
+            pass(72, 10, 40.0),
+            pass(1, 3, 5.0),
+            pass(1, 10, 88.0),
+            AzEl {
+                directions: Vec::new(),
+                ..pass(1, 10, 40.0)
+            },
+        ];
+        let selection = preferences().select_passes(&passes, NOW);
+        assert!(selection.accepted.is_empty());
+
+        let reasons: Vec<_> = selection
+            .rejected
+            .iter()
+            .map(|rejected| rejected.reasons.clone())
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                vec![Rejection::BeyondVisibility {
+                    latest_start: NOW + Duration::days(2)
+                }],
+                vec![
+                    Rejection::ElevationTooLow {
+                        max_elevation: 5.0,
+                        limit: 10.0
+                    },
+                    Rejection::TooShort {
+                        duration: Duration::minutes(3),
+                        limit: Duration::minutes(5)
+                    }
+                ],
+                vec![Rejection::ElevationTooHigh {
+                    max_elevation: 88.0,
+                    limit: 85.0
+                }],
+                vec![Rejection::MissingElevation],
+            ]
+        );
+    }
+
+    #[test]
+    fn unbounded_durations_saturate() {
+        let passes = [pass(1, 10, 40.0)];
+        let too_short = |preferences: UserPreferences| {
+            let selection = preferences.select_passes(&passes, NOW);
+            selection.accepted.is_empty()
+                && matches!(
+                    selection.rejected[0].reasons[..],
+                    [Rejection::TooShort { .. }]
+                )
+        };
+
+        for min_duration in [1e38, f32::INFINITY] {
+            assert!(too_short(UserPreferences {
+                min_duration,
+                ..preferences()
+            }));
+        }
+        assert!(!too_short(UserPreferences {
+            min_duration: f32::NAN,
+            ..preferences()
+        }));
+        // A saturated tolerance cancels a saturated limit, other tolerances leave it in place
+        for (duration_tolerance, rejected) in [
+            (1e38, false),
+            (f32::INFINITY, false),
+            (f32::NEG_INFINITY, true),
+            (f32::NAN, true),
+        ] {
+            let preferences = UserPreferences {
+                min_duration: 1e38,
+                duration_tolerance,
+                ..preferences()
+            };
+            assert_eq!(
+                too_short(preferences.clone()),
+                rejected,
+                "{duration_tolerance}"
+            );
+            if !rejected {
+                assert!(preferences.select_passes(&passes, NOW).accepted[0].within_tolerance);
+            }
+        }
+        assert!(!too_short(UserPreferences {
+            min_duration: 60.0,
+            duration_tolerance: f32::INFINITY,
+            ..preferences()
+        }));
+    }
+
+    #[test]
+    fn rejects_started_passes() {
+        let passes = [pass(-1, 10, 40.0), pass(0, 10, 40.0)];
+        let selection = preferences().select_passes(&passes, NOW);
+
+        assert_eq!(selection.accepted.len(), 1);
+        assert_eq!(selection.accepted[0].pass, &passes[1]);
+        assert_eq!(
+            selection.rejected[0].reasons,
+            [Rejection::AlreadyStarted { now: NOW }]
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! # Pricing
+//!
+//! Estimates the cost of tasks from the pricing tiers of an [`Account`].
+//!
+//! The models don't define how an account moves between its tiers, so the estimate is made
+//! against a single tier chosen by the caller, see [`Tier`] for the assumptions made about it.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::{
+    account::{Account, Tier},
+    error::Error,
+    task::Task,
+};
+
+/// The estimated cost of a single task
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct TaskCost<'a> {
+    pub task: &'a Task,
+    /// The number of seconds billed, zero for tasks which are not billable
+    pub billable_seconds: u32,
+    /// The cost, rounded to the nearest cent
+    pub cost: Decimal,
+}
+
+/// A per-task and total cost breakdown for a set of tasks
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct CostEstimate<'a> {
+    /// The tier the tasks were priced at
+    pub tier: &'a Tier,
+    /// The cost of each task, in the order provided
+    pub tasks: Vec<TaskCost<'a>>,
+    /// The sum of the rounded task costs
+    pub total: Decimal,
+}
+
+impl Account {
+    /// The pricing tier with the provided [`Tier::tier`]
+    pub fn pricing_tier(&self, tier: u32) -> Option<&Tier> {
+        self.tiers.iter().find(|candidate| candidate.tier == tier)
+    }
+
+    /// Estimates the cost of the provided tasks at one of the account's pricing tiers
+    ///
+    /// Each billable task costs its duration in minutes times [`Tier::price`], rounded to the
+    /// cent.
+    pub fn estimate_cost<'a>(
+        &'a self,
+        tier: u32,
+        tasks: &'a [Task],
+    ) -> Result<CostEstimate<'a>, Error> {
+        let tier = self
+            .pricing_tier(tier)
+            .ok_or(Error::UnknownPricingTier(tier))?;
+
+        let tasks: Vec<_> = tasks
+            .iter()
+            .map(|task| {
+                let billable_seconds = if task.billable {
+                    task.duration_in_seconds
+                } else {
+                    0
+                };
+                let minutes = Decimal::from(billable_seconds) / Decimal::from(60);
+                let cost = (minutes * tier.price)
+                    .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+
+                TaskCost {
+                    task,
+                    billable_seconds,
+                    cost,
+                }
+            })
+            .collect();
+        let total = tasks.iter().map(|cost| cost.cost).sum();
+
+        Ok(CostEstimate { tier, tasks, total })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use time::{Duration, OffsetDateTime};
+
+    use super::*;
+    use crate::fixtures;
+
+    fn account(tiers: &[(u32, Decimal)]) -> Account {
+        Account {
+            tiers: tiers
+                .iter()
+                .map(|(tier, price)| Tier {
+                    tier: *tier,
+                    price: *price,
+                    links: HashMap::new(),
+                })
+                .collect(),
+            ..fixtures::account()
+        }
+    }
+
+    fn task(hour: i64, seconds: u32, billable: bool) -> Task {
+        Task {
+            billable,
+            ..fixtures::task(OffsetDateTime::UNIX_EPOCH + Duration::hours(hour), seconds)
+        }
+    }
+
+    #[test]
+    fn single_tier() {
+        let account = account(&[(0, Decimal::new(1, 1))]);
+        let tasks = [task(0, 600, true), task(1, 600, false), task(2, 30, true)];
+
+        let estimate = account.estimate_cost(0, &tasks).unwrap();
+        let costs: Vec<_> = estimate.tasks.iter().map(|cost| cost.cost).collect();
+        assert_eq!(
+            costs,
+            vec![Decimal::new(100, 2), Decimal::ZERO, Decimal::new(5, 2)]
+        );
+        assert_eq!(estimate.total, Decimal::new(105, 2));
+    }
+
+    #[test]
+    fn chosen_tier() {
+        let account = account(&[(1, Decimal::new(200, 2)), (2, Decimal::new(150, 2))]);
+        let tasks = [task(0, 600, true), task(1, 90, true)];
+
+        let estimate = account.estimate_cost(2, &tasks).unwrap();
+        assert_eq!(estimate.tier.tier, 2);
+        assert_eq!(estimate.tasks[0].cost, Decimal::new(1500, 2));
+        assert_eq!(estimate.tasks[1].cost, Decimal::new(225, 2));
+        assert_eq!(estimate.total, Decimal::new(1725, 2));
+    }
+
+    #[test]
+    fn unknown_tier() {
+        assert_eq!(
+            account(&[]).estimate_cost(0, &[]),
+            Err(Error::UnknownPricingTier(0))
+        );
+        assert_eq!(
+            account(&[(1, Decimal::ONE)]).estimate_cost(2, &[]),
+            Err(Error::UnknownPricingTier(2))
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! # Properties
+//!
+//! Resolves the properties which apply to a pass from the separate property maps of a
+//! [`Site`], its [`SiteConfiguration`], the configuration's [`SiteHardware`], and an
+//! [`Override`].
+//!
+//! Layers are applied in that order, so a later layer replaces the value of an earlier one.
+//! Hardware is applied in the order it is listed in the configuration.
+
+use std::collections::{BTreeMap, HashMap};
+
+pub mod catalog;
+
+use crate::{
+    site::{Site, SiteConfiguration, SiteHardware},
+    task_override::Override,
+};
+
+/// The layer which supplied a property value
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum PropertySource {
+    Site { name: String },
+    Configuration { name: String },
+    Hardware { name: String },
+    Override { name: String },
+}
+
+impl std::fmt::Display for PropertySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertySource::Site { name } => write!(f, "site '{name}'"),
+            PropertySource::Configuration { name } => write!(f, "configuration '{name}'"),
+            PropertySource::Hardware { name } => write!(f, "hardware '{name}'"),
+            PropertySource::Override { name } => write!(f, "override '{name}'"),
+        }
+    }
+}
+
+/// The effective value of a property, and where it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct ResolvedProperty {
+    pub value: String,
+    pub source: PropertySource,
+    /// Values from earlier layers which were replaced, in the order they were applied
+    pub shadowed: Vec<(PropertySource, String)>,
+}
+
+/// The effective property set for a pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct EffectiveProperties {
+    pub properties: BTreeMap<String, ResolvedProperty>,
+}
+
+impl EffectiveProperties {
+    /// Layers the properties of each of the provided models
+    pub fn resolve(
+        site: Option<&Site>,
+        configuration: Option<&SiteConfiguration>,
+        task_override: Option<&Override>,
+    ) -> Self {
+        let mut resolved = Self::default();
+
+        if let Some(site) = site {
+            let source = PropertySource::Site {
+                name: site.name.clone(),
+            };
+            resolved.apply(source, site.properties.as_ref());
+        }
+
+        if let Some(configuration) = configuration {
+            let source = PropertySource::Configuration {
+                name: configuration.name.clone(),
+            };
+            resolved.apply(source, configuration.properties.as_ref());
+
+            let hardware: &[SiteHardware] = configuration.hardware.as_deref().unwrap_or_default();
+            for hardware in hardware {
+                let source = PropertySource::Hardware {
+                    name: hardware.name.clone(),
+                };
+                resolved.apply(source, hardware.properties.as_ref());
+            }
+        }
+
+        if let Some(task_override) = task_override {
+            let source = PropertySource::Override {
+                name: task_override.name.clone(),
+            };
+            resolved.apply(source, Some(&task_override.properties));
+        }
+
+        resolved
+    }
+
+    /// Applies a layer on top of the current properties
+    pub fn apply(&mut self, source: PropertySource, properties: Option<&HashMap<String, String>>) {
+        let Some(properties) = properties else {
+            return;
+        };
+
+        for (key, value) in properties {
+            match self.properties.get_mut(key) {
+                Some(existing) => {
+                    let previous_source = std::mem::replace(&mut existing.source, source.clone());
+                    let previous_value = std::mem::replace(&mut existing.value, value.clone());
+                    existing.shadowed.push((previous_source, previous_value));
+                }
+                None => {
+                    self.properties.insert(
+                        key.clone(),
+                        ResolvedProperty {
+                            value: value.clone(),
+                            source: source.clone(),
+                            shadowed: Vec::new(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.properties
+            .get(key)
+            .map(|property| property.value.as_str())
+    }
+
+    /// The layer which supplied the effective value of the property
+    pub fn source(&self, key: &str) -> Option<&PropertySource> {
+        self.properties.get(key).map(|property| &property.source)
+    }
+
+    /// The effective properties, without provenance
+    pub fn to_map(&self) -> HashMap<String, String> {
+        self.properties
+            .iter()
+            .map(|(key, property)| (key.clone(), property.value.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+
+    use super::*;
+
+    fn props(pairs: &[(&str, &str)]) -> Option<HashMap<String, String>> {
+        Some(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn layered_precedence() {
+        let site = Site {
+            properties: props(&[("a", "site"), ("b", "site"), ("c", "site")]),
+            ..fixtures::site()
+        };
+        let configuration = SiteConfiguration {
+            properties: props(&[("b", "config"), ("c", "config")]),
+            hardware: Some(vec![SiteHardware {
+                properties: props(&[("c", "hardware")]),
+                ..fixtures::site_hardware("Modem")
+            }]),
+            ..fixtures::site_configuration()
+        };
+        let task_override = Override {
+            properties: props(&[("d", "override")]).unwrap(),
+            ..fixtures::task_override()
+        };
+
+        let resolved =
+            EffectiveProperties::resolve(Some(&site), Some(&configuration), Some(&task_override));
+        assert_eq!(resolved.get("a"), Some("site"));
+        assert_eq!(resolved.get("b"), Some("config"));
+        assert_eq!(resolved.get("c"), Some("hardware"));
+        assert_eq!(resolved.get("d"), Some("override"));
+        assert_eq!(
+            resolved.source("c"),
+            Some(&PropertySource::Hardware {
+                name: "Modem".into()
+            })
+        );
+        assert_eq!(
+            resolved.properties["c"].shadowed,
+            vec![
+                (
+                    PropertySource::Site {
+                        name: "Site".into()
+                    },
+                    "site".into()
+                ),
+                (
+                    PropertySource::Configuration {
+                        name: "Config".into()
+                    },
+                    "config".into()
+                ),
+            ]
+        );
+        assert_eq!(resolved.to_map().len(), 4);
+    }
+}
@@ -0,0 +1,373 @@
+//! A catalog of known property keys, used to validate property maps before submission.
+//!
+//! The catalog starts empty, teams register the keys their sites and overrides use with
+//! [`PropertyCatalog::register`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use time::Duration;
+
+use crate::task_override::Override;
+
+/// The type, and allowed range, of the value of a property
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum PropertyType {
+    Int {
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    Float {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Bool,
+    /// A duration, either in seconds or with one of the units `ms`, `s`, `m` or `h`
+    Duration {
+        min: Option<Duration>,
+        max: Option<Duration>,
+    },
+    /// One of a fixed set of values, compared case-sensitively
+    Enum(Vec<String>),
+    /// A frequency, either in MHz or with one of the units `Hz`, `kHz`, `MHz` or `GHz`
+    Frequency {
+        min_mghz: Option<f64>,
+        max_mghz: Option<f64>,
+    },
+}
+
+impl std::fmt::Display for PropertyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyType::Int { .. } => f.write_str("an integer"),
+            PropertyType::Float { .. } => f.write_str("a number"),
+            PropertyType::Bool => f.write_str("true or false"),
+            PropertyType::Duration { .. } => f.write_str("a duration"),
+            PropertyType::Enum(values) => write!(f, "one of {}", values.join(", ")),
+            PropertyType::Frequency { .. } => f.write_str("a frequency"),
+        }
+    }
+}
+
+/// A parsed property value
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum PropertyValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Duration(Duration),
+    Enum(String),
+    /// The frequency in MHz
+    Frequency(f64),
+}
+
+/// A known property key
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct PropertyDefinition {
+    pub key: String,
+    pub typ: PropertyType,
+    pub description: String,
+}
+
+impl PropertyDefinition {
+    pub fn new(key: impl Into<String>, typ: PropertyType, description: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            typ,
+            description: description.into(),
+        }
+    }
+
+    /// Parses the value, checking it against the type and range of the definition
+    pub fn parse(&self, value: &str) -> Result<PropertyValue, PropertyIssue> {
+        let malformed = || PropertyIssue::Malformed {
+            key: self.key.clone(),
+            value: value.to_owned(),
+            expected: self.typ.to_string(),
+        };
+        let out_of_range = || PropertyIssue::OutOfRange {
+            key: self.key.clone(),
+            value: value.to_owned(),
+        };
+        let trimmed = value.trim();
+
+        match &self.typ {
+            PropertyType::Int { min, max } => {
+                let parsed: i64 = trimmed.parse().map_err(|_| malformed())?;
+                within(parsed, *min, *max)
+                    .then_some(PropertyValue::Int(parsed))
+                    .ok_or_else(out_of_range)
+            }
+            PropertyType::Float { min, max } => {
+                let parsed: f64 = trimmed
+                    .parse()
+                    .ok()
+                    .filter(|parsed: &f64| parsed.is_finite())
+                    .ok_or_else(malformed)?;
+                within(parsed, *min, *max)
+                    .then_some(PropertyValue::Float(parsed))
+                    .ok_or_else(out_of_range)
+            }
+            PropertyType::Bool => match trimmed.to_ascii_lowercase().as_str() {
+                "true" => Ok(PropertyValue::Bool(true)),
+                "false" => Ok(PropertyValue::Bool(false)),
+                _ => Err(malformed()),
+            },
+            PropertyType::Duration { min, max } => {
+                let seconds = parse_duration_seconds(trimmed).ok_or_else(malformed)?;
+                let parsed = Duration::checked_seconds_f64(seconds).ok_or_else(out_of_range)?;
+                within(parsed, *min, *max)
+                    .then_some(PropertyValue::Duration(parsed))
+                    .ok_or_else(out_of_range)
+            }
+            PropertyType::Enum(values) => values
+                .iter()
+                .any(|allowed| allowed == trimmed)
+                .then(|| PropertyValue::Enum(trimmed.to_owned()))
+                .ok_or_else(malformed),
+            PropertyType::Frequency { min_mghz, max_mghz } => {
+                let parsed = parse_frequency_mghz(trimmed).ok_or_else(malformed)?;
+                within(parsed, *min_mghz, *max_mghz)
+                    .then_some(PropertyValue::Frequency(parsed))
+                    .ok_or_else(out_of_range)
+            }
+        }
+    }
+}
+
+fn within<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+}
+
+/// Splits a value such as `"2.5kHz"` into its number and unit
+fn split_unit(value: &str) -> Option<(f64, &str)> {
+    let idx = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(idx);
+    let number: f64 = number.trim().parse().ok()?;
+    number.is_finite().then_some((number, unit.trim()))
+}
+
+/// The number of seconds in a duration such as `90s` or `1.5h`, which may not fit a [`Duration`]
+fn parse_duration_seconds(value: &str) -> Option<f64> {
+    let (number, unit) = split_unit(value)?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => return None,
+    };
+
+    seconds.is_finite().then_some(seconds)
+}
+
+fn parse_frequency_mghz(value: &str) -> Option<f64> {
+    let (number, unit) = split_unit(value)?;
+    let mghz = match unit.to_ascii_lowercase().as_str() {
+        "hz" => number / 1_000_000.0,
+        "khz" => number / 1_000.0,
+        "" | "mhz" => number,
+        "ghz" => number * 1_000.0,
+        _ => return None,
+    };
+
+    Some(mghz)
+}
+
+/// A problem with a property, found when validating against a [`PropertyCatalog`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum PropertyIssue {
+    UnknownKey {
+        key: String,
+    },
+    Malformed {
+        key: String,
+        value: String,
+        expected: String,
+    },
+    OutOfRange {
+        key: String,
+        value: String,
+    },
+}
+
+impl std::fmt::Display for PropertyIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyIssue::UnknownKey { key } => write!(f, "Unknown property '{key}'"),
+            PropertyIssue::Malformed {
+                key,
+                value,
+                expected,
+            } => write!(f, "Property '{key}' is '{value}', expected {expected}"),
+            PropertyIssue::OutOfRange { key, value } => {
+                write!(f, "Property '{key}' is '{value}', which is out of range")
+            }
+        }
+    }
+}
+
+/// A registry of known property keys
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PropertyCatalog {
+    definitions: BTreeMap<String, PropertyDefinition>,
+}
+
+impl PropertyCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key to the catalog, returning the definition it replaced, if any
+    pub fn register(&mut self, definition: PropertyDefinition) -> Option<PropertyDefinition> {
+        self.definitions.insert(definition.key.clone(), definition)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PropertyDefinition> {
+        self.definitions.get(key)
+    }
+
+    pub fn definitions(&self) -> impl Iterator<Item = &PropertyDefinition> {
+        self.definitions.values()
+    }
+
+    /// Parses the value of a single property
+    pub fn parse(&self, key: &str, value: &str) -> Result<PropertyValue, PropertyIssue> {
+        self.get(key)
+            .ok_or_else(|| PropertyIssue::UnknownKey {
+                key: key.to_owned(),
+            })?
+            .parse(value)
+    }
+
+    /// Checks every property in the map, returning the issues sorted by key
+    pub fn validate(&self, properties: &HashMap<String, String>) -> Vec<PropertyIssue> {
+        let mut issues: Vec<_> = properties
+            .iter()
+            .filter_map(|(key, value)| self.parse(key, value).err())
+            .collect();
+        issues.sort();
+        issues
+    }
+
+    pub fn validate_override(&self, task_override: &Override) -> Vec<PropertyIssue> {
+        self.validate(&task_override.properties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> PropertyCatalog {
+        let mut catalog = PropertyCatalog::new();
+        catalog.register(PropertyDefinition::new(
+            "my.prop",
+            PropertyType::Int {
+                min: Some(0),
+                max: Some(100),
+            },
+            "A percentage",
+        ));
+        catalog.register(PropertyDefinition::new(
+            "modem.lock_timeout",
+            PropertyType::Duration {
+                min: None,
+                max: Some(Duration::minutes(5)),
+            },
+            "How long to wait for lock",
+        ));
+        catalog.register(PropertyDefinition::new(
+            "modem.frequency",
+            PropertyType::Frequency {
+                min_mghz: Some(2000.0),
+                max_mghz: Some(2300.0),
+            },
+            "Downlink frequency",
+        ));
+        catalog.register(PropertyDefinition::new(
+            "modem.mode",
+            PropertyType::Enum(vec!["BPSK".into(), "QPSK".into()]),
+            "Modulation",
+        ));
+        catalog
+    }
+
+    #[test]
+    fn parse_values() {
+        let catalog = catalog();
+        assert_eq!(catalog.parse("my.prop", "20"), Ok(PropertyValue::Int(20)));
+        assert_eq!(
+            catalog.parse("modem.lock_timeout", "90s"),
+            Ok(PropertyValue::Duration(Duration::seconds(90)))
+        );
+        assert_eq!(
+            catalog.parse("modem.lock_timeout", "2m"),
+            Ok(PropertyValue::Duration(Duration::minutes(2)))
+        );
+        assert_eq!(
+            catalog.parse("modem.frequency", "2.2 GHz"),
+            Ok(PropertyValue::Frequency(2200.0))
+        );
+        assert_eq!(
+            catalog.parse("modem.mode", "QPSK"),
+            Ok(PropertyValue::Enum("QPSK".into()))
+        );
+    }
+
+    #[test]
+    fn huge_duration_is_out_of_range() {
+        let catalog = catalog();
+        for value in ["99999999999999999999999h", "-99999999999999999999999m"] {
+            assert!(
+                matches!(
+                    catalog.parse("modem.lock_timeout", value),
+                    Err(PropertyIssue::OutOfRange { .. })
+                ),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_reports_issues() {
+        let properties: HashMap<String, String> = [
+            ("my.prop", "200"),
+            ("modem.mode", "8PSK"),
+            ("modem.lock_timeout", "soon"),
+            ("made.up", "1"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v.to_owned()))
+        .collect();
+
+        let issues = catalog().validate(&properties);
+        assert_eq!(
+            issues,
+            vec![
+                PropertyIssue::UnknownKey {
+                    key: "made.up".into()
+                },
+                PropertyIssue::Malformed {
+                    key: "modem.lock_timeout".into(),
+                    value: "soon".into(),
+                    expected: "a duration".into(),
+                },
+                PropertyIssue::Malformed {
+                    key: "modem.mode".into(),
+                    value: "8PSK".into(),
+                    expected: "one of BPSK, QPSK".into(),
+                },
+                PropertyIssue::OutOfRange {
+                    key: "my.prop".into(),
+                    value: "200".into(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! # Query
+//!
+//! Typed query parameters for the Spring Data REST collection endpoints of Freedom resources,
+//! covering paging, sorting, projections and the named search endpoints.
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use strum::{AsRefStr, EnumString, VariantArray};
+use time::{OffsetDateTime, UtcOffset, format_description::well_known::Rfc3339};
+use url::Url;
+
+use crate::{
+    account::Account,
+    band::Band,
+    environment::Environment,
+    error::Error,
+    id::Resource,
+    satellite::Satellite,
+    satellite_configuration::SatelliteConfiguration,
+    site::{Site, SiteConfiguration},
+    task::{Task, TaskRequest, TaskStatusType},
+    task_override::Override,
+    user::User,
+};
+
+/// A resource whose collection endpoint can be queried
+pub trait Queryable: Resource {
+    /// The fields the collection can be sorted by, named as they are serialized
+    type SortField: AsRef<str> + fmt::Debug + Clone + Eq + Hash;
+}
+
+/// The direction of a sort
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, AsRefStr, EnumString,
+)]
+#[strum(serialize_all = "lowercase")]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// A named search endpoint of a collection, found at `<collection>/search/<name>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Search {
+    name: String,
+    params: Vec<(String, String)>,
+}
+
+impl Search {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            params: Vec::new(),
+        }
+    }
+
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+/// The query of a collection endpoint of `T`
+///
+/// ```
+/// # use freedom_models::{environment::Environment, query::{Query, SortDirection, TaskRequestSort}, task::TaskRequest};
+/// let url = Query::<TaskRequest>::new()
+///     .page(1)
+///     .size(50)
+///     .sort(TaskRequestSort::TargetDate, SortDirection::Desc)
+///     .url(&Environment::Test)
+///     .unwrap();
+///
+/// assert_eq!(
+///     url.as_str(),
+///     "https://test-api.atlasground.com/api/requests?page=1&size=50&sort=targetDate%2Cdesc"
+/// );
+/// ```
+pub struct Query<T: Queryable> {
+    page: Option<u32>,
+    size: Option<u32>,
+    sort: Vec<(T::SortField, SortDirection)>,
+    projection: Option<String>,
+    search: Option<Search>,
+    model: PhantomData<fn() -> T>,
+}
+
+impl<T: Queryable> Default for Query<T> {
+    fn default() -> Self {
+        Self {
+            page: None,
+            size: None,
+            sort: Vec::new(),
+            projection: None,
+            search: None,
+            model: PhantomData,
+        }
+    }
+}
+
+impl<T: Queryable> Query<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queries a named search endpoint, rather than the collection itself
+    pub fn search(search: Search) -> Self {
+        Self {
+            search: Some(search),
+            ..Self::default()
+        }
+    }
+
+    /// The zero-based page to fetch
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Adds a sort, earlier sorts take precedence over later ones
+    pub fn sort(mut self, field: T::SortField, direction: SortDirection) -> Self {
+        self.sort.push((field, direction));
+        self
+    }
+
+    pub fn projection(mut self, projection: impl Into<String>) -> Self {
+        self.projection = Some(projection.into());
+        self
+    }
+
+    /// The query parameters, in the order they appear in the URL
+    pub fn params(&self) -> Vec<(String, String)> {
+        let mut params = self
+            .search
+            .as_ref()
+            .map(|search| search.params.clone())
+            .unwrap_or_default();
+        if let Some(page) = self.page {
+            params.push(("page".into(), page.to_string()));
+        }
+        if let Some(size) = self.size {
+            params.push(("size".into(), size.to_string()));
+        }
+        for (field, direction) in &self.sort {
+            let sort = format!("{},{}", field.as_ref(), direction.as_ref());
+            params.push(("sort".into(), sort));
+        }
+        if let Some(projection) = &self.projection {
+            params.push(("projection".into(), projection.clone()));
+        }
+
+        params
+    }
+
+    /// The URL of the query, within the environment
+    ///
+    /// Fails when the base of the environment can't hold a path, such as a `mailto:` URL.
+    pub fn url(&self, environment: &Environment) -> Result<Url, Error> {
+        let path = match &self.search {
+            Some(search) => format!("{}/search/{}", T::COLLECTION, search.name),
+            None => T::COLLECTION.to_owned(),
+        };
+        let base = environment.base();
+        let mut url = base
+            .join(&path)
+            .map_err(|e| Error::InvalidQuery(format!("{base}{path}: {e}")))?;
+
+        let params = self.params();
+        if !params.is_empty() {
+            url.query_pairs_mut().extend_pairs(params);
+        }
+
+        Ok(url)
+    }
+}
+
+impl Query<TaskRequest> {
+    /// Task requests whose target date falls within the range
+    ///
+    /// Fails when either date can't be written as an RFC 3339 timestamp, such as a year
+    /// before 0 or after 9999.
+    pub fn target_date_between(start: OffsetDateTime, end: OffsetDateTime) -> Result<Self, Error> {
+        Ok(Self::search(
+            Search::new("findAllByTargetDateBetween")
+                .with_param("start", format_date_time(start)?)
+                .with_param("end", format_date_time(end)?),
+        ))
+    }
+
+    /// Task requests whose latest status matches
+    pub fn status(status: TaskStatusType) -> Self {
+        Self::search(Search::new("findAllByStatus").with_param("status", status.as_ref()))
+    }
+}
+
+/// Formats the time as an RFC 3339 timestamp in UTC
+fn format_date_time(date_time: OffsetDateTime) -> Result<String, Error> {
+    date_time
+        .to_offset(UtcOffset::UTC)
+        .format(&Rfc3339)
+        .map_err(|e| Error::InvalidQuery(format!("{date_time}: {e}")))
+}
+
+impl<T: Queryable> Clone for Query<T> {
+    fn clone(&self) -> Self {
+        Self {
+            page: self.page,
+            size: self.size,
+            sort: self.sort.clone(),
+            projection: self.projection.clone(),
+            search: self.search.clone(),
+            model: PhantomData,
+        }
+    }
+}
+
+impl<T: Queryable> PartialEq for Query<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.page == other.page
+            && self.size == other.size
+            && self.sort == other.sort
+            && self.projection == other.projection
+            && self.search == other.search
+    }
+}
+
+impl<T: Queryable> Eq for Query<T> {}
+
+impl<T: Queryable> Hash for Query<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.page.hash(state);
+        self.size.hash(state);
+        self.sort.hash(state);
+        self.projection.hash(state);
+        self.search.hash(state);
+    }
+}
+
+impl<T: Queryable> fmt::Debug for Query<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Query")
+            .field("collection", &T::COLLECTION)
+            .field("page", &self.page)
+            .field("size", &self.size)
+            .field("sort", &self.sort)
+            .field("projection", &self.projection)
+            .field("search", &self.search)
+            .finish()
+    }
+}
+
+macro_rules! sort_fields {
+    ($($(#[$meta:meta])* $model:ty => $sort:ident { $($(#[$field_meta:meta])* $field:ident),* $(,)? })*) => {
+        $(
+            $(#[$meta])*
+            #[derive(
+                Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, AsRefStr, EnumString,
+                VariantArray,
+            )]
+            #[strum(serialize_all = "camelCase")]
+            #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+            pub enum $sort {
+                $($(#[$field_meta])* $field,)*
+            }
+
+            impl Queryable for $model {
+                type SortField = $sort;
+            }
+        )*
+    };
+}
+
+sort_fields!(
+    /// The sortable fields of an [`Account`]
+    Account => AccountSort { Created, Modified, Name, ExternalId }
+    /// The sortable fields of a [`Band`]
+    Band => BandSort {
+        Created,
+        Modified,
+        Name,
+        #[strum(serialize = "type")]
+        BandType,
+        FrequencyMghz,
+        AccountName,
+    }
+    /// The sortable fields of an [`Override`]
+    Override => OverrideSort { Created, Modified, Name }
+    /// The sortable fields of a [`Satellite`]
+    Satellite => SatelliteSort { Created, Modified, Name, NoradCatId, AccountName }
+    /// The sortable fields of a [`SatelliteConfiguration`]
+    SatelliteConfiguration => SatelliteConfigurationSort { Created, Modified, Name, AccountName }
+    /// The sortable fields of a [`Site`]
+    Site => SiteSort { Created, Modified, Name, BaseFpsPort }
+    /// The sortable fields of a [`SiteConfiguration`]
+    SiteConfiguration => SiteConfigurationSort { Created, Modified, Name, ConfigurationSeconds }
+    /// The sortable fields of a [`Task`]
+    Task => TaskSort { Created, Modified, Start, End, DurationInSeconds }
+    /// The sortable fields of a [`TaskRequest`]
+    TaskRequest => TaskRequestSort {
+        Created,
+        Modified,
+        #[strum(serialize = "type")]
+        TaskType,
+        TargetDate,
+        EarliestStart,
+        LatestStart,
+        Duration,
+    }
+    /// The sortable fields of a [`User`]
+    User => UserSort { Created, Modified, FirstName, LastName, Email }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collection_url() {
+        let url = Query::<Satellite>::new().url(&Environment::Test).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://test-api.atlasground.com/api/satellites"
+        );
+
+        let url = Query::<Site>::new()
+            .sort(SiteSort::Name, SortDirection::Asc)
+            .sort(SiteSort::Created, SortDirection::Desc)
+            .projection("summary")
+            .url(&Environment::Production)
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://api.atlasground.com/api/sites?sort=name%2Casc&sort=created%2Cdesc&projection=summary"
+        );
+    }
+
+    #[test]
+    fn sort_field_names() {
+        assert_eq!(TaskRequestSort::TaskType.as_ref(), "type");
+        assert_eq!(TaskRequestSort::EarliestStart.as_ref(), "earliestStart");
+        assert_eq!(SatelliteSort::NoradCatId.as_ref(), "noradCatId");
+        assert_eq!(BandSort::FrequencyMghz.as_ref(), "frequencyMghz");
+    }
+
+    #[test]
+    fn search_url() {
+        let gateway = Environment::custom(Url::parse("https://gateway.internal/api").unwrap());
+
+        let query = Query::<TaskRequest>::search(
+            Search::new("findAllByStatus").with_param("status", "QUEUED_PASS"),
+        )
+        .size(100);
+        assert_eq!(
+            query.url(&gateway).unwrap().as_str(),
+            "https://gateway.internal/api/requests/search/findAllByStatus?status=QUEUED_PASS&size=100"
+        );
+        assert_eq!(query.clone(), query);
+        assert_ne!(query, Query::new());
+
+        let mailto = Environment::custom(Url::parse("mailto:ops@example.com").unwrap());
+        assert!(matches!(
+            Query::<Site>::new().url(&mailto),
+            Err(Error::InvalidQuery(_))
+        ));
+    }
+
+    #[test]
+    fn task_request_searches() {
+        use time::macros::datetime;
+
+        let gateway = Environment::custom(Url::parse("https://gateway.internal/api").unwrap());
+
+        let url = Query::<TaskRequest>::target_date_between(
+            datetime!(2025-08-01 00:00 UTC),
+            datetime!(2025-08-02 12:30:15.5 +02:00),
+        )
+        .unwrap()
+        .size(100)
+        .url(&gateway)
+        .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://gateway.internal/api/requests/search/findAllByTargetDateBetween\
+             ?start=2025-08-01T00%3A00%3A00Z&end=2025-08-02T10%3A30%3A15.5Z&size=100"
+        );
+
+        let url = Query::<TaskRequest>::status(TaskStatusType::QueuedPass)
+            .url(&gateway)
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://gateway.internal/api/requests/search/findAllByStatus?status=QUEUED_PASS"
+        );
+
+        assert!(matches!(
+            Query::<TaskRequest>::target_date_between(
+                datetime!(-0001-01-01 00:00 UTC),
+                datetime!(2025-08-01 00:00 UTC),
+            ),
+            Err(Error::InvalidQuery(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sort_fields_are_serialized() {
+        use serde::Serialize;
+
+        use crate::fixtures;
+
+        fn assert_serialized<T: Queryable + Serialize>(model: T)
+        where
+            T::SortField: VariantArray,
+        {
+            let value = serde_json::to_value(model).unwrap();
+            for field in T::SortField::VARIANTS {
+                assert!(
+                    value.get(field.as_ref()).is_some(),
+                    "{} are not serialized with {field:?}",
+                    T::COLLECTION
+                );
+            }
+        }
+
+        assert_serialized(fixtures::account());
+        assert_serialized(fixtures::band("Band"));
+        assert_serialized(fixtures::task_override());
+        assert_serialized(fixtures::satellite());
+        assert_serialized(fixtures::satellite_configuration());
+        assert_serialized(fixtures::site());
+        assert_serialized(fixtures::site_configuration());
+        assert_serialized(fixtures::task(time::OffsetDateTime::UNIX_EPOCH, 60));
+        assert_serialized(fixtures::task_request());
+        assert_serialized(fixtures::user());
+    }
+}
@@ -0,0 +1,195 @@
+//! # Realtime
+//!
+//! Describes how to reach the realtime Front-end Processor Service (FPS) for a pass.
+
+use std::net::{IpAddr, Ipv6Addr};
+
+use crate::{account::Account, band::Band, error::Error, site::Site};
+
+/// A single FPS endpoint, serving one band of a pass
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct FpsEndpoint<'a> {
+    pub band: &'a Band,
+    pub host: String,
+    pub port: u16,
+}
+
+impl FpsEndpoint<'_> {
+    /// The `host:port` address of the endpoint, with IPv6 hosts in brackets
+    pub fn address(&self) -> String {
+        match self.host.parse::<Ipv6Addr>() {
+            Ok(_) => format!("[{}]:{}", self.host, self.port),
+            Err(_) => format!("{}:{}", self.host, self.port),
+        }
+    }
+}
+
+/// The realtime FPS endpoints for the bands of a task, along with the sources allowed to connect
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct FpsConnection<'a> {
+    pub account: &'a Account,
+    pub endpoints: Vec<FpsEndpoint<'a>>,
+}
+
+impl<'a> FpsConnection<'a> {
+    /// Builds the endpoints for each band from the account's FPS host and the site's base port
+    ///
+    /// Each band is paired with its port offset from [`Site::base_fps_port`]. The models don't
+    /// carry the offset of a band, so it must come from the site's FPS configuration; it is never
+    /// inferred from the order of the bands. The endpoints are sorted by port, and an offset
+    /// assigned to several bands is rejected.
+    pub fn new(
+        account: &'a Account,
+        site: &Site,
+        bands: impl IntoIterator<Item = (&'a Band, u16)>,
+    ) -> Result<Self, Error> {
+        let host = account
+            .fps_host_name
+            .as_deref()
+            .filter(|host| !host.is_empty())
+            .ok_or(Error::MissingFpsHost)?;
+
+        let mut endpoints = bands
+            .into_iter()
+            .map(|(band, offset)| {
+                let port = i64::from(site.base_fps_port) + i64::from(offset);
+                let port = u16::try_from(port).map_err(|_| Error::InvalidFpsPort(port))?;
+
+                Ok(FpsEndpoint {
+                    band,
+                    host: host.to_owned(),
+                    port,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        endpoints.sort_by_key(|endpoint| endpoint.port);
+        if let Some(pair) = endpoints
+            .windows(2)
+            .find(|pair| pair[0].port == pair[1].port)
+        {
+            return Err(Error::InvalidFpsPort(pair[0].port.into()));
+        }
+
+        Ok(Self { account, endpoints })
+    }
+
+    /// The endpoint serving the band with the provided name
+    pub fn endpoint_for(&self, band_name: &str) -> Option<&FpsEndpoint<'a>> {
+        self.endpoints
+            .iter()
+            .find(|endpoint| endpoint.band.name == band_name)
+    }
+
+    /// Whether the source address is whitelisted for realtime connections by the account
+    pub fn is_source_allowed(&self, source: IpAddr) -> bool {
+        self.account.realtime_allows(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use ipnet::Ipv4Net;
+
+    use crate::account::AccountCidr;
+    use crate::fixtures;
+
+    use super::*;
+
+    fn account(fps_host_name: Option<&str>) -> Account {
+        Account {
+            access_realtime_cidr: vec![AccountCidr {
+                name: "Office".into(),
+                cidr: Ipv4Net::new_assert(Ipv4Addr::new(192, 168, 1, 96), 28).into(),
+            }],
+            fps_host_name: fps_host_name.map(String::from),
+            ..fixtures::account()
+        }
+    }
+
+    fn site(base_fps_port: i32) -> Site {
+        Site {
+            base_fps_port,
+            ..fixtures::site()
+        }
+    }
+
+    #[test]
+    fn endpoints_per_band() {
+        let account = account(Some("fps.example.com"));
+        let (downlink, uplink) = (fixtures::band("downlink"), fixtures::band("uplink"));
+        let connection =
+            FpsConnection::new(&account, &site(40000), [(&downlink, 0), (&uplink, 1)]).unwrap();
+
+        assert_eq!(connection.endpoints.len(), 2);
+        assert_eq!(
+            connection.endpoint_for("downlink").unwrap().address(),
+            "fps.example.com:40000"
+        );
+        assert_eq!(connection.endpoint_for("uplink").unwrap().port, 40001);
+    }
+
+    #[test]
+    fn ipv6_endpoint_address() {
+        let downlink = fixtures::band("downlink");
+        let account = account(Some("::1"));
+        let connection = FpsConnection::new(&account, &site(40000), [(&downlink, 0)]).unwrap();
+
+        let address = connection.endpoints[0].address();
+        assert_eq!(address, "[::1]:40000");
+        assert!(address.parse::<std::net::SocketAddr>().is_ok());
+
+        let account = self::account(Some("192.168.1.1"));
+        let connection = FpsConnection::new(&account, &site(40000), [(&downlink, 0)]).unwrap();
+        assert_eq!(connection.endpoints[0].address(), "192.168.1.1:40000");
+    }
+
+    #[test]
+    fn endpoints_ignore_band_order() {
+        let account = account(Some("fps.example.com"));
+        let (downlink, uplink) = (fixtures::band("downlink"), fixtures::band("uplink"));
+
+        let forward =
+            FpsConnection::new(&account, &site(40000), [(&downlink, 0), (&uplink, 5)]).unwrap();
+        let reversed =
+            FpsConnection::new(&account, &site(40000), [(&uplink, 5), (&downlink, 0)]).unwrap();
+        assert_eq!(forward, reversed);
+        assert_eq!(reversed.endpoint_for("uplink").unwrap().port, 40005);
+    }
+
+    #[test]
+    fn missing_host_or_bad_port() {
+        let downlink = fixtures::band("downlink");
+        let uplink = fixtures::band("uplink");
+        assert_eq!(
+            FpsConnection::new(&account(None), &site(40000), [(&downlink, 0)]),
+            Err(Error::MissingFpsHost)
+        );
+        assert_eq!(
+            FpsConnection::new(&account(Some("fps")), &site(-1), [(&downlink, 0)]),
+            Err(Error::InvalidFpsPort(-1))
+        );
+        assert_eq!(
+            FpsConnection::new(
+                &account(Some("fps")),
+                &site(40000),
+                [(&downlink, 2), (&uplink, 2)]
+            ),
+            Err(Error::InvalidFpsPort(40002))
+        );
+    }
+
+    #[test]
+    fn source_whitelist() {
+        let account = account(Some("fps.example.com"));
+        let connection = FpsConnection::new(&account, &site(40000), []).unwrap();
+
+        assert!(connection.is_source_allowed(Ipv4Addr::new(192, 168, 1, 100).into()));
+        assert!(!connection.is_source_allowed(Ipv4Addr::new(10, 0, 0, 1).into()));
+        assert!(!connection.is_source_allowed("::1".parse().unwrap()));
+    }
+}
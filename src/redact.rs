@@ -0,0 +1,696 @@
+//! # Redact
+//!
+//! Produces copies of models with personal and sensitive fields masked, so real payloads can be
+//! shared without leaking customer data.
+//!
+//! Identifying values such as names and emails are replaced by a salted hash, so the same input
+//! always redacts to the same output with a given [`Redactor`]. This keeps relationships intact,
+//! for example an account name redacts identically on an [`Account`] and on its [`Band`]s.
+//! Secrets are never hashed, only masked.
+//!
+//! The salt is random unless provided, so a guessed value can't be confirmed by hashing it.
+//!
+//! Free-form maps, such as meta data and override properties, keep their keys but have every
+//! value masked, since the crate can't tell what they hold.
+//!
+//! Requires the `redact` feature.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::OnceLock;
+
+use ipnet::IpNet;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    account::{Account, AccountCidr, Tier},
+    azel::{AzEl, Direction, Location},
+    band::{Band, IoConfiguration},
+    gateway_licenses::{
+        ActivateRequest, CreateRequest, DeactivateRequest, RegenerateRequest, RegenerateResponse,
+        VerifyRequest, VerifyResponse, View, ViewOne,
+    },
+    satellite::{Satellite, TwoLineElement},
+    satellite_configuration::SatelliteConfiguration,
+    secret::Secret,
+    site::{Host, Site, SiteConfiguration, SiteHardware},
+    status::{HardwareMetric, PassMetric, SiteMetric, ValueMetric},
+    task::{Task, TaskRequest, TaskStatus, TaskStatusEvent},
+    task_override::{ConfigurationDetails, Override, SatelliteDetails},
+    user::{User, UserPreferences},
+};
+
+const MASK: &str = "[REDACTED]";
+
+/// Masks and hashes sensitive values
+///
+/// Hashes are salted, so the output of two redactors with different salts cannot be correlated.
+/// Reuse a redactor, or share its salt, to redact several payloads consistently.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    salt: Secret,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Redactor {
+    /// A redactor with a random salt
+    pub fn new() -> Self {
+        Self {
+            salt: random_salt(),
+        }
+    }
+
+    pub fn with_salt(salt: impl Into<Secret>) -> Self {
+        Self { salt: salt.into() }
+    }
+
+    fn digest(&self, value: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.expose().as_bytes());
+        hasher.update(value.as_bytes());
+        hasher.finalize().into()
+    }
+
+    /// A short, stable hash of the value
+    pub fn hash(&self, value: &str) -> String {
+        self.digest(value)[..6]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Replaces an identifying value, such as a name, with its hash
+    pub fn name(&self, value: &str) -> String {
+        format!("redacted-{}", self.hash(value))
+    }
+
+    /// Replaces an email with an address on a reserved domain
+    ///
+    /// Emails are compared case-insensitively.
+    pub fn email(&self, value: &str) -> String {
+        format!("{}@redacted.invalid", self.hash(&value.to_lowercase()))
+    }
+
+    /// Maps an address into a range reserved for benchmarking (IPv4) or documentation (IPv6)
+    pub fn ip(&self, ip: IpAddr) -> IpAddr {
+        let digest = self.digest(&ip.to_string());
+        match ip {
+            IpAddr::V4(_) => {
+                let bits = u32::from_be_bytes([0, digest[0], digest[1], digest[2]]) & 0x0001_ffff;
+                Ipv4Addr::from(u32::from(Ipv4Addr::new(198, 18, 0, 0)) | bits).into()
+            }
+            IpAddr::V6(_) => {
+                let mut octets = [0; 16];
+                octets[..4].copy_from_slice(&[0x20, 0x01, 0x0d, 0xb8]);
+                octets[4..].copy_from_slice(&digest[..12]);
+                Ipv6Addr::from(octets).into()
+            }
+        }
+    }
+
+    /// Maps a range to a range of the same size within the reserved ranges of [`Redactor::ip`]
+    ///
+    /// Ranges larger than the reserved range of their family are mapped to the whole reserved
+    /// range, so the result never leaves it.
+    pub fn net(&self, net: IpNet) -> IpNet {
+        let min_prefix = match net {
+            IpNet::V4(_) => 15,
+            IpNet::V6(_) => 32,
+        };
+
+        IpNet::new(self.ip(net.network()), net.prefix_len().max(min_prefix))
+            .map(|masked| masked.trunc())
+            .unwrap_or(net)
+    }
+
+    pub fn host(&self, host: &Host) -> Host {
+        match host {
+            Host::Ip(ip) => Host::Ip(self.ip(*ip)),
+            Host::Hostname(name) | Host::Invalid(name) => {
+                Host::Hostname(format!("{}.redacted.invalid", self.hash(name)))
+            }
+        }
+    }
+
+    pub fn secret(&self, _: &Secret) -> Secret {
+        Secret::new(MASK)
+    }
+
+    fn meta_data(
+        &self,
+        meta_data: &Option<HashMap<String, String>>,
+    ) -> Option<HashMap<String, String>> {
+        meta_data.as_ref().map(|meta_data| self.values(meta_data))
+    }
+
+    /// Masks every value of the map, keeping its keys
+    fn values(&self, map: &HashMap<String, String>) -> HashMap<String, String> {
+        map.keys()
+            .map(|key| (key.clone(), MASK.to_owned()))
+            .collect()
+    }
+
+    /// Masks every string within the JSON value, keeping its shape
+    #[cfg(feature = "serde")]
+    fn json(&self, value: &serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+
+        match value {
+            Value::String(_) => Value::String(MASK.to_owned()),
+            Value::Array(items) => items.iter().map(|item| self.json(item)).collect(),
+            Value::Object(object) => Value::Object(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), self.json(value)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+/// 256 bits of salt drawn from the randomly seeded keys of the standard library's hasher
+fn random_salt() -> Secret {
+    let state = std::collections::hash_map::RandomState::new();
+    let salt: String = (0..4u64)
+        .map(|word| {
+            let mut hasher = state.build_hasher();
+            hasher.write_u64(word);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect();
+
+    Secret::new(salt)
+}
+
+/// A model which can produce a copy of itself with sensitive fields redacted
+pub trait Redact {
+    fn redact_with(&self, redactor: &Redactor) -> Self;
+
+    /// Redacts using a [`Redactor`] shared by the whole process
+    ///
+    /// Its salt is random, so the output is consistent within the process but can't be correlated
+    /// with the output of another.
+    fn redact(&self) -> Self
+    where
+        Self: Sized,
+    {
+        static SHARED: OnceLock<Redactor> = OnceLock::new();
+
+        self.redact_with(SHARED.get_or_init(Redactor::new))
+    }
+}
+
+impl<T: Redact> Redact for Vec<T> {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        self.iter().map(|item| item.redact_with(redactor)).collect()
+    }
+}
+
+impl<T: Redact> Redact for Option<T> {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        self.as_ref().map(|item| item.redact_with(redactor))
+    }
+}
+
+/// Implements [`Redact`] for models which hold nothing sensitive
+macro_rules! redact_unchanged {
+    ($($model:ty),* $(,)?) => {
+        $(
+            impl Redact for $model {
+                fn redact_with(&self, _: &Redactor) -> Self {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+redact_unchanged!(
+    Tier,
+    Location,
+    Direction,
+    AzEl,
+    IoConfiguration,
+    CreateRequest,
+    RegenerateRequest,
+    ActivateRequest,
+    DeactivateRequest,
+    VerifyResponse,
+    ViewOne,
+    View,
+    TwoLineElement,
+    PassMetric,
+    SiteMetric,
+    HardwareMetric,
+    ValueMetric,
+    TaskStatus,
+    TaskStatusEvent,
+    ConfigurationDetails,
+    SatelliteDetails,
+    UserPreferences,
+);
+
+impl Redact for AccountCidr {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            cidr: redactor.net(self.cidr),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for Account {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            name: redactor.name(&self.name),
+            storage_key: redactor.secret(&self.storage_key),
+            storage_group: self
+                .storage_group
+                .as_deref()
+                .map(|group| redactor.name(group)),
+            access_realtime_cidr: self.access_realtime_cidr.redact_with(redactor),
+            access_api_cidr: self.access_api_cidr.redact_with(redactor),
+            external_id: self.external_id.as_deref().map(|id| redactor.name(id)),
+            fps_host_name: self
+                .fps_host_name
+                .as_deref()
+                .map(|host| redactor.host(&Host::parse_lenient(host)).to_string()),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for Band {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            account_name: self.account_name.as_deref().map(|name| redactor.name(name)),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for RegenerateResponse {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            license_key: redactor.secret(&self.license_key),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for VerifyRequest {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            license_key: redactor.secret(&self.license_key),
+        }
+    }
+}
+
+impl Redact for Satellite {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            account_name: redactor.name(&self.account_name),
+            meta_data: redactor.meta_data(&self.meta_data),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for SatelliteConfiguration {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            account_name: redactor.name(&self.account_name),
+            meta_data: redactor.meta_data(&self.meta_data),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for SiteHardware {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            ip: self.ip.as_ref().map(|host| redactor.host(host)),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for Site {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for SiteConfiguration {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            hardware: self.hardware.redact_with(redactor),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for Task {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            // The paths of the results hold the storage identifiers of the account
+            file_results: self
+                .file_results
+                .iter()
+                .map(|path| redactor.name(path))
+                .collect(),
+            meta_data: redactor.meta_data(&self.meta_data),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for TaskRequest {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            meta_data: redactor.meta_data(&self.meta_data),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for Override {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            properties: redactor.values(&self.properties),
+            ..self.clone()
+        }
+    }
+}
+
+impl Redact for User {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            first_name: redactor.name(&self.first_name),
+            last_name: redactor.name(&self.last_name),
+            email: redactor.email(&self.email),
+            internal_meta_data: redactor.meta_data(&self.internal_meta_data),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Redact for crate::pagination::Paginated<T>
+where
+    T: serde::de::DeserializeOwned + Redact + Clone,
+{
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            items: self.items.redact_with(redactor),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Redact for crate::pagination::LenientPaginated<T>
+where
+    T: serde::de::DeserializeOwned + Redact + Clone,
+{
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            items: self.items.redact_with(redactor),
+            errors: self
+                .errors
+                .iter()
+                .map(|error| crate::pagination::ItemError {
+                    raw: MASK.to_owned(),
+                    ..error.clone()
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Redact for crate::utils::Embedded<T>
+where
+    T: serde::de::DeserializeOwned + Redact + Clone,
+{
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            items: self.items.redact_with(redactor),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Redact for crate::utils::Content<T>
+where
+    T: serde::de::DeserializeOwned + crate::Hateoas + Redact,
+{
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            inner: self.inner.redact_with(redactor),
+        }
+    }
+}
+
+/// The relations aren't typed, so every string they hold is masked
+///
+/// To keep the fields which aren't sensitive, deserialize the relation with
+/// [`EmbeddedMap::get`](crate::utils::EmbeddedMap::get) and redact the model instead.
+#[cfg(feature = "serde")]
+impl Redact for crate::utils::EmbeddedMap {
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            relations: self
+                .relations
+                .iter()
+                .map(|(relation, value)| (relation.clone(), redactor.json(value)))
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Redact for crate::links::Linked<T>
+where
+    T: Redact,
+{
+    fn redact_with(&self, redactor: &Redactor) -> Self {
+        Self {
+            inner: self.inner.redact_with(redactor),
+            links: self.links.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn user(first_name: &str, email: &str) -> User {
+        User {
+            first_name: first_name.into(),
+            email: email.into(),
+            ..fixtures::user()
+        }
+    }
+
+    #[test]
+    fn user_is_redacted_consistently() {
+        let first = user("Ada", "ada@example.com").redact();
+        let second = user("Ada", "ADA@example.com").redact();
+
+        assert_ne!(first.first_name, "Ada");
+        assert!(!first.email.contains("example.com"));
+        assert_eq!(first, second);
+
+        let salted = user("Ada", "ada@example.com").redact_with(&Redactor::with_salt("salt"));
+        assert_ne!(first.email, salted.email);
+        let again = user("Ada", "ada@example.com").redact_with(&Redactor::with_salt("salt"));
+        assert_eq!(salted, again);
+    }
+
+    #[test]
+    fn default_salt_is_random() {
+        let email = "ada@example.com";
+        assert_ne!(Redactor::new().email(email), Redactor::new().email(email));
+        assert_ne!(
+            Redactor::new().email(email),
+            Redactor::with_salt("").email(email)
+        );
+    }
+
+    #[test]
+    fn ips_stay_in_reserved_ranges() {
+        let redactor = Redactor::new();
+        let v4 = redactor.ip("10.1.2.3".parse().unwrap());
+        assert!("198.18.0.0/15".parse::<IpNet>().unwrap().contains(&v4));
+        let v6 = redactor.ip("fe80::1".parse().unwrap());
+        assert!("2001:db8::/32".parse::<IpNet>().unwrap().contains(&v6));
+
+        let net = redactor.net("10.1.2.0/24".parse().unwrap());
+        assert_eq!(net.prefix_len(), 24);
+        assert_eq!(net, redactor.net("10.1.2.0/24".parse().unwrap()));
+
+        let reserved_v4: IpNet = "198.18.0.0/15".parse().unwrap();
+        assert_eq!(redactor.net("10.0.0.0/8".parse().unwrap()), reserved_v4);
+        assert_eq!(redactor.net("172.16.0.0/12".parse().unwrap()), reserved_v4);
+        assert_eq!(redactor.net("0.0.0.0/0".parse().unwrap()), reserved_v4);
+        let net = redactor.net("10.0.0.0/16".parse().unwrap());
+        assert_eq!(net.prefix_len(), 16);
+        assert!(reserved_v4.contains(&net));
+
+        let reserved_v6: IpNet = "2001:db8::/32".parse().unwrap();
+        assert_eq!(redactor.net("fc00::/7".parse().unwrap()), reserved_v6);
+    }
+
+    #[test]
+    fn site_internal_meta_data_is_masked() {
+        let meta_data = Some(HashMap::from([("owner".to_string(), "ACME".to_string())]));
+        let masked = Some(HashMap::from([("owner".to_string(), MASK.to_string())]));
+
+        let site = Site {
+            internal_meta_data: meta_data.clone(),
+            ..fixtures::site()
+        };
+        assert_eq!(site.redact().internal_meta_data, masked);
+
+        let configuration = SiteConfiguration {
+            internal_meta_data: meta_data,
+            ..fixtures::site_configuration()
+        };
+        assert_eq!(configuration.redact().internal_meta_data, masked);
+    }
+
+    #[test]
+    fn secrets_are_masked() {
+        let hardware = SiteHardware {
+            ip: Some(Host::Hostname("modem.customer.com".into())),
+            port_comms: Some(5000),
+            ..fixtures::site_hardware("Modem")
+        };
+        let redacted = hardware.redact();
+        assert!(!redacted.ip.unwrap().to_string().contains("customer"));
+        assert_eq!(redacted.port_comms, Some(5000));
+
+        let secret = Redactor::new().secret(&Secret::new("hunter2"));
+        assert_eq!(secret.expose(), MASK);
+    }
+
+    fn meta_data() -> Option<HashMap<String, String>> {
+        Some(HashMap::from([(
+            "mission".to_string(),
+            "ACME-1".to_string(),
+        )]))
+    }
+
+    fn masked() -> Option<HashMap<String, String>> {
+        Some(HashMap::from([("mission".to_string(), MASK.to_string())]))
+    }
+
+    #[test]
+    fn task_file_results_are_hashed() {
+        let path = "acme-storage/group-7/task-1/iq.bin";
+        let task = Task {
+            file_results: vec![path.into(), path.into()],
+            ..fixtures::task(time::OffsetDateTime::UNIX_EPOCH, 60)
+        };
+
+        let redacted = task.redact();
+        assert!(!redacted.file_results[0].contains("acme"));
+        assert_eq!(redacted.file_results[0], redacted.file_results[1]);
+    }
+
+    #[test]
+    fn customer_meta_data_is_masked() {
+        let satellite = crate::satellite::Satellite {
+            meta_data: meta_data(),
+            ..fixtures::satellite()
+        };
+        assert_eq!(satellite.redact().meta_data, masked());
+
+        let configuration = SatelliteConfiguration {
+            meta_data: meta_data(),
+            ..fixtures::satellite_configuration()
+        };
+        assert_eq!(configuration.redact().meta_data, masked());
+
+        let task = Task {
+            meta_data: meta_data(),
+            ..fixtures::task(time::OffsetDateTime::UNIX_EPOCH, 60)
+        };
+        assert_eq!(task.redact().meta_data, masked());
+
+        let request = TaskRequest {
+            meta_data: meta_data(),
+            ..fixtures::task_request()
+        };
+        assert_eq!(request.redact().meta_data, masked());
+    }
+
+    #[test]
+    fn override_properties_are_masked() {
+        let ov = Override {
+            properties: meta_data().unwrap(),
+            ..fixtures::task_override()
+        };
+        assert_eq!(Some(ov.redact().properties), masked());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn embedded_map_strings_are_masked() {
+        let mut embedded = crate::utils::EmbeddedMap::new();
+        embedded
+            .insert(
+                "users",
+                &serde_json::json!([{ "email": "ada@example.com", "age": 36 }]),
+            )
+            .unwrap();
+
+        let redacted = embedded.redact();
+        assert_eq!(
+            redacted.relations["users"],
+            serde_json::json!([{ "email": MASK, "age": 36 }])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn linked_model_is_redacted() {
+        let links = HashMap::from([(
+            "self".to_owned(),
+            url::Url::parse("http://localhost:8080/api/users/1").unwrap(),
+        )]);
+        let user = crate::links::Linked {
+            inner: User {
+                links: links.clone(),
+                ..fixtures::user()
+            },
+            links: crate::links::LinkMap::from(&links),
+        };
+
+        let redacted = user.redact();
+        assert_ne!(redacted.email, fixtures::user().email);
+        assert_eq!(redacted.links, user.links);
+    }
+}
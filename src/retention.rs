@@ -0,0 +1,165 @@
+//! # Retention
+//!
+//! Computes when the data of a task ages out, based on [`Account::weeks_of_data_storage`].
+//!
+//! The retention period starts at the end of the task. Tasks without any
+//! [`Task::file_results`] have nothing to expire, and are left out of every listing.
+
+use time::{Duration, OffsetDateTime};
+
+use crate::{account::Account, task::Task};
+
+/// The expiry of the data of a single task
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct RetentionEntry<'a> {
+    pub task: &'a Task,
+    pub expires_at: OffsetDateTime,
+}
+
+impl RetentionEntry<'_> {
+    /// The time left before the data expires, negative once it has expired
+    pub fn remaining(&self, now: OffsetDateTime) -> Duration {
+        self.expires_at - now
+    }
+
+    pub fn files(&self) -> &[String] {
+        &self.task.file_results
+    }
+}
+
+/// The data of a set of tasks, grouped by when it expires
+///
+/// Each group is sorted by expiry, soonest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct RetentionReport<'a> {
+    /// The time the report was computed against
+    pub now: OffsetDateTime,
+    /// Data which has already expired
+    pub expired: Vec<RetentionEntry<'a>>,
+    /// Data which expires within the horizon of the report
+    pub expiring: Vec<RetentionEntry<'a>>,
+    /// Data which is retained beyond the horizon of the report
+    pub retained: Vec<RetentionEntry<'a>>,
+}
+
+impl Account {
+    /// How long the data of a task is kept after the task ends
+    pub fn data_retention(&self) -> Duration {
+        Duration::weeks(self.weeks_of_data_storage.into())
+    }
+
+    /// When the file results of the task expire
+    pub fn data_expires_at(&self, task: &Task) -> OffsetDateTime {
+        task.end.saturating_add(self.data_retention())
+    }
+
+    /// Tasks whose data has not yet expired, but will within `horizon` of `now`
+    pub fn data_expiring_within<'a>(
+        &self,
+        tasks: &'a [Task],
+        now: OffsetDateTime,
+        horizon: Duration,
+    ) -> Vec<RetentionEntry<'a>> {
+        self.retention_report(tasks, now, horizon).expiring
+    }
+
+    /// Groups the data of the tasks into expired, expiring within `horizon` of `now`, and retained
+    pub fn retention_report<'a>(
+        &self,
+        tasks: &'a [Task],
+        now: OffsetDateTime,
+        horizon: Duration,
+    ) -> RetentionReport<'a> {
+        let mut entries: Vec<_> = tasks
+            .iter()
+            .filter(|task| !task.file_results.is_empty())
+            .map(|task| RetentionEntry {
+                task,
+                expires_at: self.data_expires_at(task),
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.expires_at);
+
+        let cutoff = now.saturating_add(horizon);
+        let mut report = RetentionReport {
+            now,
+            expired: Vec::new(),
+            expiring: Vec::new(),
+            retained: Vec::new(),
+        };
+        for entry in entries {
+            if entry.expires_at <= now {
+                report.expired.push(entry);
+            } else if entry.expires_at <= cutoff {
+                report.expiring.push(entry);
+            } else {
+                report.retained.push(entry);
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::fixtures;
+
+    fn account(weeks_of_data_storage: u32) -> Account {
+        Account {
+            weeks_of_data_storage,
+            ..fixtures::account()
+        }
+    }
+
+    fn task(end: OffsetDateTime, files: &[&str]) -> Task {
+        Task {
+            file_results: files.iter().map(|file| file.to_string()).collect(),
+            ..fixtures::task(end - Duration::minutes(10), 600)
+        }
+    }
+
+    #[test]
+    fn expiry_from_end() {
+        let account = account(2);
+        let task = task(datetime!(2025 - 01 - 01 12:00).assume_utc(), &["a.bin"]);
+        assert_eq!(
+            account.data_expires_at(&task),
+            datetime!(2025 - 01 - 15 12:00).assume_utc()
+        );
+    }
+
+    #[test]
+    fn report_groups() {
+        let account = account(1);
+        let now = datetime!(2025 - 01 - 10 00:00).assume_utc();
+        let tasks = [
+            task(
+                datetime!(2025 - 01 - 09 00:00).assume_utc(),
+                &["retained.bin"],
+            ),
+            task(
+                datetime!(2025 - 01 - 01 00:00).assume_utc(),
+                &["expired.bin"],
+            ),
+            task(datetime!(2025 - 01 - 04 00:00).assume_utc(), &["soon.bin"]),
+            task(datetime!(2025 - 01 - 04 00:00).assume_utc(), &[]),
+        ];
+
+        let report = account.retention_report(&tasks, now, Duration::days(2));
+        assert_eq!(report.expired.len(), 1);
+        assert_eq!(report.expired[0].files(), ["expired.bin"]);
+        assert_eq!(report.expiring.len(), 1);
+        assert_eq!(report.expiring[0].files(), ["soon.bin"]);
+        assert_eq!(report.expiring[0].remaining(now), Duration::days(1));
+        assert_eq!(report.retained.len(), 1);
+
+        let expiring = account.data_expiring_within(&tasks, now, Duration::days(2));
+        assert_eq!(expiring, report.expiring);
+    }
+}
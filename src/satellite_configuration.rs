@@ -5,6 +5,7 @@ use time::OffsetDateTime;
 use url::Url;
 
 use crate::Hateoas;
+use crate::links::LinkRel;
 
 #[cfg(feature = "serde")]
 use super::utils;
@@ -52,4 +53,8 @@ impl Hateoas for SatelliteConfiguration {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
 }
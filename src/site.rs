@@ -1,15 +1,166 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use strum::{AsRefStr, EnumString};
 use time::OffsetDateTime;
 use url::Url;
 
 use crate::Hateoas;
 use crate::azel::Location;
+use crate::links::LinkRel;
 
 #[cfg(feature = "serde")]
 use super::utils;
 
+/// The network host of a piece of site hardware
+///
+/// When deserialized, values which are neither an IP address nor a valid hostname are kept as
+/// [`Host::Invalid`] rather than failing the whole model.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum Host {
+    Ip(IpAddr),
+    Hostname(String),
+    /// The raw value, which could not be parsed as an IP address or hostname
+    Invalid(String),
+}
+
+impl Host {
+    /// Parses the host, keeping the raw string as [`Host::Invalid`] on failure
+    pub fn parse_lenient(value: &str) -> Self {
+        value
+            .parse()
+            .unwrap_or_else(|_| Host::Invalid(value.to_owned()))
+    }
+
+    /// The IP address of the host, if it is one
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            Host::Ip(ip) => Some(*ip),
+            _ => None,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        !matches!(self, Host::Invalid(_))
+    }
+
+    pub fn as_str(&self) -> std::borrow::Cow<'_, str> {
+        match self {
+            Host::Ip(ip) => ip.to_string().into(),
+            Host::Hostname(name) | Host::Invalid(name) => name.as_str().into(),
+        }
+    }
+}
+
+impl std::str::FromStr for Host {
+    type Err = crate::error::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(ip) = value.parse::<IpAddr>() {
+            return Ok(Host::Ip(ip));
+        }
+
+        match invalid_hostname(value) {
+            None => Ok(Host::Hostname(value.to_owned())),
+            Some(reason) => Err(crate::error::Error::InvalidHost(format!(
+                "{value}: {reason}"
+            ))),
+        }
+    }
+}
+
+/// Why the value is not a hostname per RFC 1123, if it isn't one
+///
+/// A final label made of digits only is rejected as well, so shorthand IPv4 forms such as
+/// `10.0.0` are never taken for a hostname.
+fn invalid_hostname(value: &str) -> Option<&'static str> {
+    let name = value.strip_suffix('.').unwrap_or(value);
+    if name.is_empty() || name.len() > 253 {
+        return Some("hostnames are 1 to 253 characters long");
+    }
+
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Some("labels are 1 to 63 characters long");
+        }
+        if !label
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        {
+            return Some("labels hold only letters, digits and hyphens");
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Some("labels can't start or end with a hyphen");
+        }
+    }
+
+    name.rsplit('.')
+        .next()
+        .filter(|last| last.bytes().all(|b| b.is_ascii_digit()))
+        .map(|_| "not a plain IP address or hostname")
+}
+
+impl std::fmt::Display for Host {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Host {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Host {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Host::parse_lenient(&raw))
+    }
+}
+
+/// The kind of a piece of site hardware
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "SCREAMING_SNAKE_CASE")
+)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, AsRefStr, EnumString)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub enum HardwareType {
+    Antenna,
+    Modem,
+    Fep,
+    Cortex,
+    Recorder,
+    Digitizer,
+    #[strum(default)]
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Other(String),
+}
+
+impl HardwareType {
+    /// The name of the hardware type as it appears in the API, including for
+    /// [`HardwareType::Other`]
+    pub fn as_str(&self) -> &str {
+        match self {
+            HardwareType::Other(other) => other.as_str(),
+            known => known.as_ref(),
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -20,7 +171,7 @@ use super::utils;
 pub struct SiteHardware {
     pub manual: bool,
     #[cfg_attr(feature = "serde", serde(default))]
-    pub ip: Option<String>,
+    pub ip: Option<Host>,
     pub name: String,
     #[cfg_attr(feature = "serde", serde(default))]
     pub specifications: Option<String>,
@@ -36,13 +187,24 @@ pub struct SiteHardware {
     )]
     pub modified: Option<OffsetDateTime>,
     #[cfg_attr(feature = "serde", serde(rename = "type", default))]
-    pub typ: Option<String>,
+    pub typ: Option<HardwareType>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub port_comms: Option<u16>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub properties: Option<HashMap<String, String>>,
 }
 
+impl SiteHardware {
+    /// The socket address used to communicate with the hardware
+    ///
+    /// Only available when the host is an IP address and a port is set, hostnames must be
+    /// resolved by the caller.
+    pub fn socket_addr(&self) -> Option<SocketAddr> {
+        let ip = self.ip.as_ref()?.ip()?;
+        Some(SocketAddr::new(ip, self.port_comms?))
+    }
+}
+
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -82,6 +244,10 @@ impl Hateoas for Site {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
 }
 
 #[cfg_attr(
@@ -126,6 +292,10 @@ impl Hateoas for SiteConfiguration {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
@@ -143,4 +313,92 @@ mod tests {
         "#;
         serde_json::from_str::<SiteHardware>(string).unwrap();
     }
+
+    fn hardware_with(ip: &str, typ: &str) -> SiteHardware {
+        let json = serde_json::json!({
+            "manual": false,
+            "name": "Testing",
+            "created": "2020-08-12T04:05:20Z",
+            "ip": ip,
+            "type": typ,
+            "portComms": 5000,
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn hardware_ip_endpoint() {
+        let hardware = hardware_with("10.0.0.12", "MODEM");
+        assert_eq!(hardware.typ, Some(HardwareType::Modem));
+        assert_eq!(
+            hardware.socket_addr(),
+            Some("10.0.0.12:5000".parse().unwrap())
+        );
+
+        let hardware = hardware_with("fe80::1", "MODEM");
+        assert_eq!(
+            hardware.socket_addr(),
+            Some("[fe80::1]:5000".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn hardware_hostname_endpoint() {
+        let hardware = hardware_with("modem-1.site.local", "SPECTRUM_ANALYZER");
+        assert_eq!(
+            hardware.ip,
+            Some(Host::Hostname("modem-1.site.local".into()))
+        );
+        assert_eq!(
+            hardware.typ,
+            Some(HardwareType::Other("SPECTRUM_ANALYZER".into()))
+        );
+        assert_eq!(hardware.socket_addr(), None);
+    }
+
+    #[test]
+    fn hardware_invalid_host_is_kept() {
+        let hardware = hardware_with("not a host!", "MODEM");
+        assert_eq!(hardware.ip, Some(Host::Invalid("not a host!".into())));
+        assert!("not a host!".parse::<Host>().is_err());
+
+        let value = serde_json::to_value(&hardware).unwrap();
+        assert_eq!(value["ip"], "not a host!");
+    }
+
+    #[test]
+    fn hardware_shorthand_ipv4_is_kept() {
+        for raw in ["10.0.0", "1234", "0x7f.1", "1.2.3.4.5", "010.0.0.1"] {
+            let hardware = hardware_with(raw, "MODEM");
+            assert_eq!(hardware.ip, Some(Host::Invalid(raw.into())), "{raw}");
+            assert!(raw.parse::<Host>().is_err(), "{raw}");
+
+            let value = serde_json::to_value(&hardware).unwrap();
+            assert_eq!(value["ip"], raw);
+        }
+    }
+
+    #[test]
+    fn hostnames_follow_rfc_1123() {
+        let long_label = "a".repeat(64);
+        let long_name = ["a"; 128].join(".");
+        for raw in [
+            "a..b",
+            "-bad-",
+            "bad-.example.com",
+            "exa%41mple.com",
+            "under_score.com",
+            "",
+            ".",
+            long_label.as_str(),
+            long_name.as_str(),
+        ] {
+            assert!(raw.parse::<Host>().is_err(), "{raw}");
+            assert_eq!(Host::parse_lenient(raw), Host::Invalid(raw.into()));
+        }
+
+        for raw in ["localhost", "modem-1.site.local", "3com.example.", "a.b2"] {
+            assert_eq!(raw.parse(), Ok(Host::Hostname(raw.into())), "{raw}");
+        }
+    }
 }
@@ -2,6 +2,12 @@
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
+use crate::{
+    id::{ResourceUri, TaskId},
+    site::{Site, SiteConfiguration},
+    task::TaskRequest,
+};
+
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -21,12 +27,12 @@ pub struct PassMetric {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
 pub struct SiteMetric {
-    pub task_id: i32,
+    pub task_id: TaskId,
     pub name: String,
-    pub task_request_uri: String,
+    pub task_request_uri: ResourceUri<TaskRequest>,
     pub configuration: String,
-    pub site_uri: String,
-    pub config_uri: String,
+    pub site_uri: ResourceUri<Site>,
+    pub config_uri: ResourceUri<SiteConfiguration>,
     #[cfg_attr(feature = "serde", serde(with = "crate::utils::timestamp"))]
     pub collected: OffsetDateTime,
     pub hardware_metrics: Vec<HardwareMetric>,
@@ -147,6 +153,9 @@ mod tests {
 }"#;
 
         let ser: PassMetric = serde_json::from_slice(json_value.as_bytes()).unwrap();
+        assert_eq!(ser.site.task_request_uri.id().get(), 1);
+        assert_eq!(ser.site.site_uri.id().get(), 1);
+        assert_eq!(ser.site.config_uri.id().get(), 1);
         let first = &ser.site.hardware_metrics[0].metrics[0];
         assert_eq!(first.type_string.as_str(), "site.hardware.modem.status");
         assert_eq!(&first.value, &Value::Bool(true));
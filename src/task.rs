@@ -6,6 +6,8 @@ use time::OffsetDateTime;
 use url::Url;
 
 use crate::Hateoas;
+use crate::id::{ResourceUri, TaskRequestId};
+use crate::links::LinkRel;
 
 #[cfg(feature = "serde")]
 use super::utils;
@@ -153,6 +155,16 @@ impl Hateoas for Task {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
+}
+
+impl Task {
+    pub fn site_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::Site)
+    }
 }
 
 #[cfg_attr(
@@ -163,8 +175,8 @@ impl Hateoas for Task {
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
 pub struct TaskStatusEvent {
-    pub task_request_id: i32,
-    pub task_request_uri: String,
+    pub task_request_id: TaskRequestId,
+    pub task_request_uri: ResourceUri<TaskRequest>,
     pub status_changes: Vec<TaskStatus>,
 }
 
@@ -232,6 +244,24 @@ impl Hateoas for TaskRequest {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
+}
+
+impl TaskRequest {
+    pub fn site_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::Site)
+    }
+
+    pub fn satellite_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::Satellite)
+    }
+
+    pub fn configuration_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::Configuration)
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
@@ -263,6 +293,7 @@ mod tests {
   ]
 }"#;
         let event: TaskStatusEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.task_request_uri.id(), event.task_request_id);
         assert_eq!(
             event.latest_status().unwrap().status,
             TaskStatusType::QueuedPass
@@ -4,6 +4,7 @@ use time::OffsetDateTime;
 use url::Url;
 
 use crate::Hateoas;
+use crate::links::LinkRel;
 
 #[cfg_attr(
     feature = "serde",
@@ -70,6 +71,24 @@ impl Hateoas for Override {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink, LinkRel::User]
+    }
+}
+
+impl Override {
+    pub fn satellite_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::Satellite)
+    }
+
+    pub fn configuration_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::Configuration)
+    }
+
+    pub fn user_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::User)
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
@@ -144,4 +163,31 @@ mod tests {
         assert_eq!(ov.name, "C-Test-1");
         assert_eq!(ov.properties.get("my.prop").unwrap(), "20");
     }
+
+    #[test]
+    fn override_links() {
+        let link = |path: &str| Url::parse(&format!("http://localhost:8080/api/{path}")).unwrap();
+        let mut ov = crate::fixtures::task_override();
+        ov.links = HashMap::from([
+            ("self".into(), link("overrides/233")),
+            ("user".into(), link("overrides/233/user")),
+        ]);
+        assert!(ov.satellite_link().is_none());
+        assert!(ov.configuration_link().is_none());
+        assert_eq!(ov.user_link(), Some(&link("overrides/233/user")));
+        assert!(ov.validate_links().is_ok());
+
+        ov.links
+            .insert("satellite".into(), link("overrides/233/satellite"));
+        ov.links
+            .insert("configuration".into(), link("overrides/233/configuration"));
+        assert_eq!(ov.satellite_link(), Some(&link("overrides/233/satellite")));
+        assert_eq!(
+            ov.configuration_link(),
+            Some(&link("overrides/233/configuration"))
+        );
+
+        ov.links.remove("user");
+        assert!(ov.validate_links().is_err());
+    }
 }
@@ -5,6 +5,7 @@ use time::OffsetDateTime;
 use url::Url;
 
 use crate::Hateoas;
+use crate::links::LinkRel;
 
 #[cfg(feature = "serde")]
 use super::utils;
@@ -70,4 +71,14 @@ impl Hateoas for User {
     fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
         &mut self.links
     }
+
+    fn expected_links(&self) -> &'static [LinkRel] {
+        &[LinkRel::SelfLink]
+    }
+}
+
+impl User {
+    pub fn account_link(&self) -> Option<&Url> {
+        self.link(&LinkRel::Account)
+    }
 }
@@ -1,15 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned};
 use url::Url;
 
-use crate::Hateoas;
+use crate::{Hateoas, error::Error};
 
 pub(crate) mod timestamp;
 
 pub fn flatten_deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
-    D: Deserializer<'de> + 'de,
+    D: Deserializer<'de>,
     T: DeserializeOwned,
 {
     let vec_of_results: Vec<Result<T, String>> = Vec::deserialize(deserializer)?;
@@ -17,19 +17,86 @@ where
     Ok(vec_of_results.into_iter().flatten().collect())
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
+/// A HAL resource holding a single embedded relation
+///
+/// When serialized, the items are embedded under [`Embedded::relation`].
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
 pub struct Embedded<T>
 where
     T: DeserializeOwned,
 {
-    #[serde(rename = "_embedded")]
-    #[serde(deserialize_with = "destructure::serde::deserialize")]
     pub items: T,
+    /// The name of the relation the items are embedded under
+    pub relation: String,
+    pub links: HashMap<String, Url>,
+}
+
+impl<T> Embedded<T>
+where
+    T: DeserializeOwned,
+{
+    pub fn new(relation: impl Into<String>, items: T) -> Self {
+        Self {
+            items,
+            relation: relation.into(),
+            links: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EmbeddedRepr<T>
+where
+    T: DeserializeOwned,
+{
+    #[serde(rename = "_embedded")]
+    #[serde(deserialize_with = "destructure::serde::deserialize_named")]
+    embedded: (String, T),
     #[serde(rename = "_links")]
     #[serde(with = "links::serde", default)]
-    pub links: HashMap<String, Url>,
+    links: HashMap<String, Url>,
+}
+
+impl<'de, T> Deserialize<'de> for Embedded<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let EmbeddedRepr {
+            embedded: (relation, items),
+            links,
+        } = EmbeddedRepr::deserialize(deserializer)?;
+
+        Ok(Self {
+            items,
+            relation,
+            links,
+        })
+    }
+}
+
+impl<T> Serialize for Embedded<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Embedded", 2)?;
+        state.serialize_field(
+            "_embedded",
+            &destructure::serde::Named(&self.relation, &self.items),
+        )?;
+        state.serialize_field("_links", &links::serde::Links(&self.links))?;
+        state.end()
+    }
 }
 
 impl<T> Hateoas for Embedded<T>
@@ -45,6 +112,126 @@ where
     }
 }
 
+/// A HAL resource holding any number of embedded relations
+///
+/// Unlike [`Embedded`], every relation is kept, by name. The relations are deserialized lazily,
+/// so each relation may hold a different type.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(not(feature = "unstable"), non_exhaustive)]
+pub struct EmbeddedMap {
+    pub relations: BTreeMap<String, serde_json::Value>,
+    pub links: HashMap<String, Url>,
+}
+
+impl EmbeddedMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Embeds the value under the relation, replacing any previous value
+    pub fn insert<T: Serialize>(
+        &mut self,
+        relation: impl Into<String>,
+        value: &T,
+    ) -> Result<(), Error> {
+        let value = serde_json::to_value(value).map_err(|e| Error::Embedded(e.to_string()))?;
+        self.relations.insert(relation.into(), value);
+        Ok(())
+    }
+
+    /// The names of the embedded relations, in order
+    pub fn relation_names(&self) -> impl Iterator<Item = &str> {
+        self.relations.keys().map(String::as_str)
+    }
+
+    pub fn contains(&self, relation: &str) -> bool {
+        self.relations.contains_key(relation)
+    }
+
+    /// Deserializes the value embedded under the relation
+    pub fn get<T: DeserializeOwned>(&self, relation: &str) -> Result<T, Error> {
+        let value = self
+            .relations
+            .get(relation)
+            .ok_or_else(|| Error::MissingEmbedded(relation.to_owned()))?;
+
+        T::deserialize(value).map_err(|e| Error::Embedded(format!("{relation}: {e}")))
+    }
+
+    /// Deserializes the only embedded relation, along with its name
+    ///
+    /// Fails with [`Error::MissingEmbedded`] when nothing is embedded, and with
+    /// [`Error::AmbiguousEmbedded`] when several relations are.
+    pub fn single<T: DeserializeOwned>(&self) -> Result<(&str, T), Error> {
+        let mut relations = self.relation_names();
+        match (relations.next(), relations.next()) {
+            (Some(relation), None) => Ok((relation, self.get(relation)?)),
+            (None, _) => Err(Error::MissingEmbedded("_embedded".into())),
+            (Some(_), Some(_)) => Err(Error::AmbiguousEmbedded(
+                self.relation_names().map(String::from).collect(),
+            )),
+        }
+    }
+
+    /// Converts the only embedded relation into an [`Embedded`]
+    pub fn into_single<T: DeserializeOwned>(self) -> Result<Embedded<T>, Error> {
+        let (relation, items) = self.single()?;
+
+        Ok(Embedded {
+            items,
+            relation: relation.to_owned(),
+            links: self.links,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EmbeddedMapRepr {
+    #[serde(rename = "_embedded", default)]
+    relations: BTreeMap<String, serde_json::Value>,
+    #[serde(rename = "_links", with = "links::serde", default)]
+    links: HashMap<String, Url>,
+}
+
+impl<'de> Deserialize<'de> for EmbeddedMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let EmbeddedMapRepr { relations, links } = EmbeddedMapRepr::deserialize(deserializer)?;
+
+        Ok(Self { relations, links })
+    }
+}
+
+impl Serialize for EmbeddedMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("EmbeddedMap", 2)?;
+        state.serialize_field("_embedded", &self.relations)?;
+        state.serialize_field("_links", &links::serde::Links(&self.links))?;
+        state.end()
+    }
+}
+
+impl Hateoas for EmbeddedMap {
+    fn get_links(&self) -> &HashMap<String, url::Url> {
+        &self.links
+    }
+
+    fn get_links_mut(&mut self) -> &mut HashMap<String, url::Url> {
+        &mut self.links
+    }
+}
+
+/// A HAL resource wrapping a model under `content`
+///
+/// The links of the resource are moved onto the inner model, and moved back out when
+/// serialized.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(not(feature = "unstable"), non_exhaustive)]
@@ -56,6 +243,38 @@ where
     pub inner: T,
 }
 
+impl<T> Content<T>
+where
+    T: DeserializeOwned + Hateoas,
+{
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T> Serialize for Content<T>
+where
+    T: DeserializeOwned + Hateoas + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        // The links are moved out next to the content, whichever way the model serializes them
+        let mut content = serde_json::to_value(&self.inner).map_err(S::Error::custom)?;
+        if let Some(object) = content.as_object_mut() {
+            object.remove("_links");
+        }
+
+        let mut state = serializer.serialize_struct("Content", 2)?;
+        state.serialize_field("content", &content)?;
+        state.serialize_field("_links", &links::serde::Links(self.inner.get_links()))?;
+        state.end()
+    }
+}
+
 pub(crate) mod content {
     pub(crate) mod serde {
         use std::collections::HashMap;
@@ -87,8 +306,6 @@ pub(crate) mod content {
             *(item_map.inner.get_links_mut()) = item_map.links;
             Ok(item_map.inner)
         }
-
-        // Currently there is no reason to write serialization logic for paginated items.
     }
 }
 
@@ -99,23 +316,34 @@ pub(crate) mod links {
 
         use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-        #[derive(Serialize, Deserialize)]
-        struct Link {
-            href: Url,
+        #[derive(Serialize)]
+        struct Link<'a> {
+            href: &'a Url,
         }
 
+        /// Serializes a link map in the `{ "rel": { "href": ... } }` shape
+        pub(crate) struct Links<'a>(pub &'a HashMap<String, Url>);
+
+        impl Serialize for Links<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serialize(self.0, serializer)
+            }
+        }
+
+        /// Deserializes the simple view of the links
+        ///
+        /// Relations holding several links keep the first one, and templated links are expanded
+        /// without any variables.
         pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Url>, D::Error>
         where
             D: Deserializer<'de>,
         {
-            use std::collections::hash_map::RandomState;
-
-            let link_map: HashMap<String, Link, RandomState> = HashMap::deserialize(deserializer)?;
+            let link_map = crate::links::LinkMap::deserialize(deserializer)?;
 
-            Ok(link_map
-                .into_iter()
-                .map(|(k, v)| (k, v.href))
-                .collect::<HashMap<_, _>>())
+            link_map.to_urls().map_err(serde::de::Error::custom)
         }
 
         pub(crate) fn serialize<S>(
@@ -129,7 +357,7 @@ pub(crate) mod links {
 
             let mut map = serializer.serialize_map(Some(val.len()))?;
             for (k, v) in val {
-                let link = Link { href: v.to_owned() };
+                let link = Link { href: v };
                 map.serialize_entry(k, &link)?;
             }
             map.end()
@@ -137,31 +365,88 @@ pub(crate) mod links {
     }
 }
 
-/// This flattens a map, deserializing it based on the first value.
+/// This flattens a map holding a single entry.
 pub(crate) mod destructure {
     pub(crate) mod serde {
-        use std::collections::HashMap;
+        use std::{fmt, marker::PhantomData};
 
-        use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+        use serde::{
+            Deserializer, Serialize, Serializer,
+            de::{DeserializeOwned, IgnoredAny, MapAccess, Visitor},
+        };
 
-        pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+        /// Deserializes the only value of the map, along with the key it was found under
+        ///
+        /// Maps holding several entries are rejected, rather than picking one arbitrarily.
+        pub(crate) fn deserialize_named<'de, D, T>(deserializer: D) -> Result<(String, T), D::Error>
         where
             D: Deserializer<'de>,
             T: DeserializeOwned,
         {
-            use serde::de::Error;
-            use std::collections::hash_map::RandomState;
+            deserializer.deserialize_map(NamedVisitor(PhantomData))
+        }
 
-            let item_map: HashMap<String, T, RandomState> = HashMap::deserialize(deserializer)?;
+        struct NamedVisitor<T>(PhantomData<fn() -> T>);
+
+        impl<'de, T> Visitor<'de> for NamedVisitor<T>
+        where
+            T: DeserializeOwned,
+        {
+            type Value = (String, T);
 
-            item_map
-                .into_iter()
-                .next()
-                .map(|(_, v)| v)
-                .ok_or(Error::custom("Missing inner item list"))
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map holding a single entry")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                use serde::de::Error;
+
+                let relation: String = map
+                    .next_key()?
+                    .ok_or(Error::custom("Missing inner item list"))?;
+                // The items are only deserialized once the map is known to hold a single entry,
+                // so several relations are always reported as ambiguous, whatever they hold
+                let items: serde_json::Value = map.next_value()?;
+
+                let mut relations = vec![relation];
+                while let Some(other) = map.next_key()? {
+                    map.next_value::<IgnoredAny>()?;
+                    relations.push(other);
+                }
+                if relations.len() > 1 {
+                    relations.sort();
+                    return Err(Error::custom(crate::error::Error::AmbiguousEmbedded(
+                        relations,
+                    )));
+                }
+
+                let items = T::deserialize(items).map_err(Error::custom)?;
+
+                Ok((relations.remove(0), items))
+            }
         }
 
-        // Currently there is no reason to write serialization logic for paginated items.
+        /// Serializes a value as a map with a single key
+        pub(crate) struct Named<'a, T>(pub &'a str, pub &'a T);
+
+        impl<T> Serialize for Named<'_, T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                use serde::ser::SerializeMap;
+
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(self.0, self.1)?;
+                map.end()
+            }
+        }
     }
 }
 
@@ -169,6 +454,116 @@ pub(crate) mod destructure {
 mod test {
     use ::time::{OffsetDateTime, PrimitiveDateTime, format_description::well_known::Iso8601};
 
+    use super::*;
+    use crate::task_override::Override;
+
+    #[test]
+    fn embedded_round_trip() {
+        let json = serde_json::json!({
+            "_embedded": { "bands": [1, 2, 3] },
+            "_links": { "self": { "href": "http://localhost:8080/api/bands" } }
+        });
+
+        let embedded: Embedded<Vec<u32>> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(embedded.relation, "bands");
+        assert_eq!(serde_json::to_value(&embedded).unwrap(), json);
+    }
+
+    #[test]
+    fn embedded_rejects_several_relations() {
+        let json = serde_json::json!({
+            "_embedded": { "bands": [1, 2], "satellites": [3] },
+            "_links": {}
+        });
+
+        let err = serde_json::from_value::<Embedded<Vec<u32>>>(json).unwrap_err();
+        assert!(err.to_string().contains("[bands, satellites]"));
+
+        let json = serde_json::json!({
+            "_embedded": { "a": ["x"], "b": [1] },
+            "_links": {}
+        });
+        let err = serde_json::from_value::<Embedded<Vec<u32>>>(json).unwrap_err();
+        assert!(err.to_string().contains("[a, b]"), "{err}");
+    }
+
+    #[test]
+    fn embedded_map() {
+        let json = serde_json::json!({
+            "_embedded": { "bands": [1, 2], "satellites": ["sat"] },
+            "_links": { "self": { "href": "http://localhost:8080/api/search" } }
+        });
+
+        let embedded: EmbeddedMap = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            embedded.relation_names().collect::<Vec<_>>(),
+            ["bands", "satellites"]
+        );
+        assert_eq!(embedded.get::<Vec<u32>>("bands").unwrap(), [1, 2]);
+        assert_eq!(embedded.get::<Vec<String>>("satellites").unwrap(), ["sat"]);
+        assert_eq!(
+            embedded.get::<Vec<u32>>("users"),
+            Err(Error::MissingEmbedded("users".into()))
+        );
+        assert!(matches!(
+            embedded.get::<Vec<u32>>("satellites"),
+            Err(Error::Embedded(_))
+        ));
+        assert_eq!(
+            embedded.single::<Vec<u32>>(),
+            Err(Error::AmbiguousEmbedded(vec![
+                "bands".into(),
+                "satellites".into()
+            ]))
+        );
+        assert_eq!(serde_json::to_value(&embedded).unwrap(), json);
+
+        assert_eq!(
+            EmbeddedMap::new().single::<Vec<u32>>(),
+            Err(Error::MissingEmbedded("_embedded".into()))
+        );
+
+        let mut single = EmbeddedMap::new();
+        single.insert("bands", &[4, 5]).unwrap();
+        let single: Embedded<Vec<u32>> = single.into_single().unwrap();
+        assert_eq!(single.relation, "bands");
+        assert_eq!(single.items, [4, 5]);
+    }
+
+    #[test]
+    fn content_round_trip() {
+        let json = serde_json::json!({
+            "content": {
+                "created": "2026-01-22T16:16:40.836788Z",
+                "name": "C-Test-1",
+                "properties": { "my.prop": "20" },
+                "satelliteDetails": null,
+                "configurationDetails": null
+            },
+            "_links": { "self": { "href": "http://localhost:8080/api/overrides/233" } }
+        });
+
+        let content: Content<Override> = serde_json::from_value(json).unwrap();
+        let value = serde_json::to_value(&content).unwrap();
+        assert!(value["content"].get("_links").is_none());
+        assert_eq!(
+            value["_links"]["self"]["href"],
+            "http://localhost:8080/api/overrides/233"
+        );
+
+        let round_trip: Content<Override> = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip, content);
+
+        // Wrappers which add the links to a map rather than a struct are handled too
+        let linked = Content::new(crate::links::Linked {
+            inner: content.inner,
+            links: Default::default(),
+        });
+        let value = serde_json::to_value(&linked).unwrap();
+        assert!(value["content"].get("_links").is_none());
+        assert_eq!(value["content"]["name"], "C-Test-1");
+    }
+
     #[test]
     fn time_deserialization() {
         let fmt = OffsetDateTime::now_utc().format(&Iso8601::DEFAULT);